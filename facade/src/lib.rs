@@ -0,0 +1,19 @@
+//! Stand-in for the `enumly` facade crate, which re-exports the derive next to the trait its
+//! expansion implements. Only the examples and tests of `enumly-derive` depend on it.
+
+pub use enumly_derive::Enumly;
+
+/// A type with a finite, ordered list of values.
+pub trait Enumly: Sized + 'static {
+    /// Number of values.
+    const COUNT: usize;
+
+    /// Every value, in index order.
+    const VARIANTS: &'static [Self];
+
+    /// Position of `self` in [`Enumly::VARIANTS`].
+    fn index(&self) -> usize;
+
+    /// The value at `index`, or `None` if `index` is not below [`Enumly::COUNT`].
+    fn from_index(index: usize) -> Option<Self>;
+}
@@ -0,0 +1,306 @@
+//! Parsing of the `#[enumly(...)]` helper attribute.
+
+use proc_macro2::Span;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, ExprRange, Ident, LitStr, Path, Token, Visibility};
+
+use crate::case::RenameRule;
+
+/// The kind of item the derive is applied to, which decides the options it accepts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Target {
+    Enum,
+    Struct,
+}
+
+impl Target {
+    fn describe(self) -> &'static str {
+        match self {
+            Target::Enum => "an enum",
+            Target::Struct => "a struct",
+        }
+    }
+}
+
+/// Options written as `#[enumly(...)]` on the enum or struct itself. Standard trait impls such
+/// as `FromStr` and `Display` are only generated when asked for, so that types with a
+/// hand-written impl keep compiling.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    pub(crate) rename_all: Option<RenameRule>,
+    pub(crate) ascii_case_insensitive: bool,
+    pub(crate) from_str: Option<Path>,
+    pub(crate) repr_conversions: Option<Path>,
+    pub(crate) map: Option<Ident>,
+    pub(crate) set: Option<Ident>,
+    pub(crate) kind: Option<Ident>,
+    pub(crate) iter: Option<Ident>,
+    pub(crate) vis: Option<Visibility>,
+    pub(crate) count: Option<Ident>,
+    pub(crate) variants: Option<Ident>,
+    pub(crate) names: Option<Ident>,
+    pub(crate) docs: Option<Ident>,
+    /// The `display` path, with the template given by `display = "..."` if any.
+    pub(crate) display: Option<(Path, Option<LitStr>)>,
+    pub(crate) serde: Option<LitStr>,
+    pub(crate) clap: Option<Path>,
+    /// The path given by `crate = "..."`.
+    pub(crate) krate: Option<Path>,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn parse(attrs: &[Attribute], target: Target) -> syn::Result<Self> {
+        let mut out = Self::default();
+
+        for attr in enumly_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                let enum_only = [
+                    "rename_all",
+                    "ascii_case_insensitive",
+                    "from_str",
+                    "repr_conversions",
+                    "kind",
+                    "names",
+                    "docs",
+                    "display",
+                    "serde",
+                    "clap",
+                ];
+                if target == Target::Struct
+                    && let Some(name) = enum_only.iter().find(|name| meta.path.is_ident(name))
+                {
+                    return Err(meta.error(format!("`{name}` can only be used on enums")));
+                }
+
+                if meta.path.is_ident("rename_all") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let rule = RenameRule::parse(&lit.value()).ok_or_else(|| {
+                        syn::Error::new(
+                            lit.span(),
+                            format!(
+                                "unknown rename rule `{}`; expected one of {}",
+                                lit.value(),
+                                RenameRule::expected(),
+                            ),
+                        )
+                    })?;
+                    set_once(&meta, &mut out.rename_all, rule)
+                } else if meta.path.is_ident("ascii_case_insensitive") {
+                    set_flag(&meta, &mut out.ascii_case_insensitive)
+                } else if meta.path.is_ident("from_str") {
+                    set_once(&meta, &mut out.from_str, meta.path.clone())
+                } else if meta.path.is_ident("repr_conversions") {
+                    set_once(&meta, &mut out.repr_conversions, meta.path.clone())
+                } else if meta.path.is_ident("map") {
+                    let ident: Ident = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.map, ident)
+                } else if meta.path.is_ident("set") {
+                    let ident: Ident = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.set, ident)
+                } else if meta.path.is_ident("kind") {
+                    let ident: Ident = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.kind, ident)
+                } else if meta.path.is_ident("iter") {
+                    let ident: Ident = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.iter, ident)
+                } else if meta.path.is_ident("vis") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let vis = lit.parse().map_err(|_| {
+                        syn::Error::new(
+                            lit.span(),
+                            "expected a visibility such as `pub` or `pub(crate)`",
+                        )
+                    })?;
+                    set_once(&meta, &mut out.vis, vis)
+                } else if meta.path.is_ident("count") {
+                    set_once(&meta, &mut out.count, item_name(&meta)?)
+                } else if meta.path.is_ident("variants") {
+                    set_once(&meta, &mut out.variants, item_name(&meta)?)
+                } else if meta.path.is_ident("names") {
+                    set_once(&meta, &mut out.names, item_name(&meta)?)
+                } else if meta.path.is_ident("docs") {
+                    set_once(&meta, &mut out.docs, item_name(&meta)?)
+                } else if meta.path.is_ident("display") {
+                    let template = match meta.input.peek(Token![=]) {
+                        true => Some(meta.value()?.parse()?),
+                        false => None,
+                    };
+                    set_once(&meta, &mut out.display, (meta.path.clone(), template))
+                } else if meta.path.is_ident("serde") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    if !cfg!(feature = "serde") {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "`serde` requires the `serde` feature of enumly",
+                        ));
+                    }
+                    set_once(&meta, &mut out.serde, lit)
+                } else if meta.path.is_ident("crate") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let path = lit.parse().map_err(|_| {
+                        syn::Error::new(lit.span(), "expected a path such as `::enumly`")
+                    })?;
+                    set_once(&meta, &mut out.krate, path)
+                } else if meta.path.is_ident("clap") {
+                    if !cfg!(feature = "clap") {
+                        return Err(meta.error("`clap` requires the `clap` feature of enumly"));
+                    }
+                    set_once(&meta, &mut out.clap, meta.path.clone())
+                } else {
+                    Err(meta.error(format!("unknown Enumly attribute on {}", target.describe())))
+                }
+            })?;
+        }
+
+        Ok(out)
+    }
+
+    /// Path of the `enumly` facade that the generated code refers to.
+    pub(crate) fn krate(&self) -> Path {
+        self.krate
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote! { ::enumly })
+    }
+
+    /// Name of the associated constant holding the number of values.
+    pub(crate) fn count_ident(&self) -> Ident {
+        item_ident(&self.count, "COUNT")
+    }
+
+    /// Name of the associated constant listing every value.
+    pub(crate) fn variants_ident(&self) -> Ident {
+        item_ident(&self.variants, "VARIANTS")
+    }
+
+    /// Name of the associated constant listing the variant names.
+    pub(crate) fn names_ident(&self) -> Ident {
+        item_ident(&self.names, "NAMES")
+    }
+
+    /// Name of the associated constant listing the variant doc comments.
+    pub(crate) fn docs_ident(&self) -> Ident {
+        item_ident(&self.docs, "DOCS")
+    }
+}
+
+fn item_ident(configured: &Option<Ident>, default: &str) -> Ident {
+    configured
+        .clone()
+        .unwrap_or_else(|| Ident::new(default, Span::call_site()))
+}
+
+/// Parses the replacement name of a generated item, such as `count = "LEN"`.
+fn item_name(meta: &ParseNestedMeta) -> syn::Result<Ident> {
+    let lit: LitStr = meta.value()?.parse()?;
+    lit.parse()
+        .map_err(|_| syn::Error::new(lit.span(), "expected an identifier"))
+}
+
+/// Options written as `#[enumly(...)]` on a single variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    pub(crate) rename: Option<LitStr>,
+    pub(crate) aliases: Vec<LitStr>,
+    pub(crate) default_fields: Option<Path>,
+    pub(crate) skip: Option<Path>,
+    pub(crate) hidden: Option<Path>,
+    pub(crate) display: Option<LitStr>,
+}
+
+impl VariantAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+
+        for attr in enumly_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.rename, lit)
+                } else if meta.path.is_ident("alias") {
+                    out.aliases.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("default_fields") {
+                    set_once(&meta, &mut out.default_fields, meta.path.clone())
+                } else if meta.path.is_ident("skip") {
+                    set_once(&meta, &mut out.skip, meta.path.clone())
+                } else if meta.path.is_ident("hidden") {
+                    set_once(&meta, &mut out.hidden, meta.path.clone())
+                } else if meta.path.is_ident("display") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.display, lit)
+                } else {
+                    Err(meta.error("unknown Enumly attribute on a variant"))
+                }
+            })?;
+        }
+
+        Ok(out)
+    }
+}
+
+/// Options written as `#[enumly(...)]` on an enumerated field of a variant or struct.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    pub(crate) range: Option<ExprRange>,
+    pub(crate) default: Option<Expr>,
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+
+        for attr in enumly_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("range") {
+                    let expr: Expr = meta.value()?.parse()?;
+                    let Expr::Range(range) = expr else {
+                        return Err(syn::Error::new(
+                            expr.span(),
+                            "expected a range such as `0..=3`",
+                        ));
+                    };
+                    set_once(&meta, &mut out.range, range)
+                } else if meta.path.is_ident("default") {
+                    let expr: Expr = meta.value()?.parse()?;
+                    set_once(&meta, &mut out.default, expr)
+                } else {
+                    Err(meta.error("unknown Enumly attribute on a field"))
+                }
+            })?;
+        }
+
+        Ok(out)
+    }
+}
+
+fn enumly_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("enumly"))
+}
+
+/// Stores `value` in `slot`, rejecting a second occurrence of the same option.
+fn set_once<T>(meta: &ParseNestedMeta, slot: &mut Option<T>, value: T) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(duplicate_error(meta));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// Sets a bare flag such as `ascii_case_insensitive`, rejecting a second occurrence.
+fn set_flag(meta: &ParseNestedMeta, flag: &mut bool) -> syn::Result<()> {
+    if *flag {
+        return Err(duplicate_error(meta));
+    }
+    *flag = true;
+    Ok(())
+}
+
+fn duplicate_error(meta: &ParseNestedMeta) -> syn::Error {
+    let name = meta
+        .path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    meta.error(format!("duplicate Enumly attribute `{name}`"))
+}
@@ -0,0 +1,75 @@
+//! `clap::ValueEnum` implementation for `#[enumly(clap)]`, available with the `clap` feature.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Path};
+
+use crate::attr::ContainerAttrs;
+use crate::{Shape, Variant, doc};
+
+/// Command-line values are accepted exactly when `FromStr` accepts them, so hidden and skipped
+/// variants have no possible value.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    clap: &Path,
+    variants: &[Variant],
+) -> syn::Result<TokenStream> {
+    if variants
+        .iter()
+        .any(|variant| matches!(variant.shape, Shape::Fields(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            clap,
+            "`clap` requires every variant to be a unit variant",
+        ));
+    }
+    // Clap matches possible values by exact case unless the argument sets `ignore_case`, which
+    // the type cannot do, so the two parsers would disagree.
+    if container.ascii_case_insensitive {
+        return Err(syn::Error::new_spanned(
+            clap,
+            "`clap` cannot follow `ascii_case_insensitive`, since clap matches values by exact \
+             case; remove it and set `ignore_case = true` on the clap argument instead",
+        ));
+    }
+
+    let name = &input.ident;
+    // Clap expects a possible value for every entry, so hidden variants are left out.
+    let value_variants = variants
+        .iter()
+        .filter(|variant| variant.is_named())
+        .map(|variant| &variant.ident);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        if !variant.is_named() {
+            return quote! { Self::#ident { .. } => ::core::option::Option::None };
+        }
+
+        let value = &variant.name;
+        let aliases = &variant.aliases;
+        let help = doc::summary(&variant.doc).map(|help| quote! { .help(#help) });
+        quote! {
+            Self::#ident => ::core::option::Option::Some(
+                ::clap::builder::PossibleValue::new(#value)
+                    #(.alias(#aliases))*
+                    #help
+            )
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::clap::ValueEnum for #name #ty_generics #where_clause {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[#(Self::#value_variants),*]
+            }
+
+            fn to_possible_value(&self) -> ::core::option::Option<::clap::builder::PossibleValue> {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
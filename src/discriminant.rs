@@ -0,0 +1,162 @@
+//! Discriminant table and conversions driven by `#[repr(...)]` and `= N` discriminants.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, DeriveInput, Ident, Meta, Token, Visibility};
+
+use crate::{Shape, Variant, trait_error_vis};
+
+/// Primitive integer types accepted inside `#[repr(...)]`.
+const INTEGER_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Returns the integer type named in `#[repr(...)]`, if any.
+pub(crate) fn repr(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+    let mut repr = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            if let Meta::Path(path) = meta
+                && let Some(ident) = path.get_ident()
+                && INTEGER_REPRS.contains(&ident.to_string().as_str())
+            {
+                repr = Some(ident.clone());
+            }
+        }
+    }
+
+    Ok(repr)
+}
+
+/// Expands to the `DISCRIMINANTS` table and the `to_discriminant`/`from_discriminant` pair.
+///
+/// Enums without an integer `repr` use `isize`, matching the compiler's default.
+pub(crate) fn expand(vis: &Visibility, repr: Option<&Ident>, variants: &[Variant]) -> TokenStream {
+    let repr = match repr {
+        Some(repr) => quote! { #repr },
+        None => quote! { isize },
+    };
+    let values = values(variants);
+    // Skipped variants keep their discriminant but are left out of the table and lookups.
+    let listed: Vec<(&Variant, &TokenStream)> = variants
+        .iter()
+        .zip(&values)
+        .filter(|(variant, _)| !matches!(variant.shape, Shape::Skipped))
+        .collect();
+    let listed_values = listed.iter().map(|(_, value)| value);
+
+    let to_arms = variants.iter().zip(&values).map(|(variant, value)| {
+        let ident = &variant.ident;
+        quote! { Self::#ident { .. } => #value }
+    });
+    let from_checks = listed.iter().map(|(variant, value)| {
+        let ident = &variant.ident;
+        quote! {
+            if value == #value {
+                return ::core::option::Option::Some(Self::#ident);
+            }
+        }
+    });
+
+    quote! {
+        #vis const DISCRIMINANTS: &'static [#repr] = &[#(#listed_values),*];
+
+        #vis const fn to_discriminant(&self) -> #repr {
+            match *self {
+                #(#to_arms,)*
+            }
+        }
+
+        #vis const fn from_discriminant(value: #repr) -> ::core::option::Option<Self> {
+            #(#from_checks)*
+            ::core::option::Option::None
+        }
+    }
+}
+
+/// Expands to `TryFrom<repr>` for the enum, `From<enum>` for the repr, and the error type
+/// returned for values that match no variant.
+pub(crate) fn expand_conversions(input: &DeriveInput, repr: &Ident) -> TokenStream {
+    let name = &input.ident;
+    let vis = trait_error_vis(input);
+    let error = format_ident!("TryFrom{}Error", name.unraw(), span = name.span());
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let enum_name = name.unraw().to_string();
+    let error_doc = format!(
+        "An error returned when a `{repr}` is not the discriminant of any [`{enum_name}`] variant."
+    );
+
+    quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #error {
+            value: #repr,
+        }
+
+        impl #error {
+            /// Returns the value that matched no variant.
+            #vis const fn value(&self) -> #repr {
+                self.value
+            }
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, "invalid {} discriminant `{}`", #enum_name, self.value)
+            }
+        }
+
+        impl ::core::error::Error for #error {}
+
+        impl #impl_generics ::core::convert::TryFrom<#repr> for #name #ty_generics #where_clause {
+            type Error = #error;
+
+            fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+                match Self::from_discriminant(value) {
+                    ::core::option::Option::Some(variant) => ::core::result::Result::Ok(variant),
+                    ::core::option::Option::None => ::core::result::Result::Err(#error { value }),
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #repr #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                value.to_discriminant()
+            }
+        }
+    }
+}
+
+/// Computes each variant's discriminant as an expression, continuing implicit variants by
+/// counting up from the last explicit value the same way the compiler does.
+fn values(variants: &[Variant]) -> Vec<TokenStream> {
+    let mut base = None;
+    let mut previous_offset = None;
+
+    variants
+        .iter()
+        .map(|variant| {
+            let offset = match (&variant.discriminant, previous_offset) {
+                (Some(explicit), _) => {
+                    base = Some(explicit);
+                    0
+                }
+                (None, Some(previous)) => previous + 1,
+                (None, None) => 0,
+            };
+            previous_offset = Some(offset);
+
+            let offset_lit = Literal::usize_unsuffixed(offset);
+            match base {
+                Some(base) if offset == 0 => quote! { (#base) },
+                Some(base) => quote! { ((#base) + #offset_lit) },
+                None => quote! { #offset_lit },
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+//! `Display` implementation, optionally driven by `#[enumly(display = "...")]` templates.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitStr};
+
+use crate::{Shape, Variant};
+
+/// A value a template can refer to.
+enum Placeholder {
+    Name,
+    Index,
+    Discriminant,
+}
+
+/// `template` is the enum-wide template, which variant templates override. `discriminants`
+/// tells whether `to_discriminant` is generated for the enum.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    template: Option<&LitStr>,
+    variants: &[Variant],
+    discriminants: bool,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            let body = match variant.display.as_ref().or(template) {
+                Some(template) => {
+                    let (format, placeholders) = parse(template)?;
+                    let args = placeholders
+                        .iter()
+                        .map(|placeholder| match placeholder {
+                            Placeholder::Name => Ok(quote! { self.as_str() }),
+                            Placeholder::Index if matches!(variant.shape, Shape::Skipped) => {
+                                Err(syn::Error::new(
+                                    template.span(),
+                                    format!(
+                                        "`{{index}}` cannot be used for skipped variant `{ident}`; \
+                                         give it its own `display` template"
+                                    ),
+                                ))
+                            }
+                            Placeholder::Index => Ok(quote! { self.index() }),
+                            Placeholder::Discriminant if !discriminants => Err(syn::Error::new(
+                                template.span(),
+                                "`{discriminant}` is only available for enums without flattened \
+                                 variants",
+                            )),
+                            Placeholder::Discriminant => Ok(quote! { self.to_discriminant() }),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    quote! { ::core::write!(f, #format #(, #args)*) }
+                }
+                None => quote! { f.pad(self.as_str()) },
+            };
+            Ok(quote! { Self::#ident { .. } => #body })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
+
+/// Turns a template into a `format!` string with positional `{}` holes and the placeholder
+/// filling each hole.
+fn parse(template: &LitStr) -> syn::Result<(String, Vec<Placeholder>)> {
+    let value = template.value();
+    let mut format = String::with_capacity(value.len());
+    let mut placeholders = Vec::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let mut key = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => key.push(c),
+                        None => {
+                            return Err(syn::Error::new(
+                                template.span(),
+                                "unclosed `{` in display template; write `{{` for a literal brace",
+                            ));
+                        }
+                    }
+                }
+                placeholders.push(match key.as_str() {
+                    "name" => Placeholder::Name,
+                    "index" => Placeholder::Index,
+                    "discriminant" => Placeholder::Discriminant,
+                    _ => {
+                        return Err(syn::Error::new(
+                            template.span(),
+                            format!(
+                                "unknown placeholder `{{{key}}}`; expected `{{name}}`, \
+                                 `{{index}}` or `{{discriminant}}`"
+                            ),
+                        ));
+                    }
+                });
+                format.push_str("{}");
+            }
+            '}' => {
+                return Err(syn::Error::new(
+                    template.span(),
+                    "unmatched `}` in display template; write `}}` for a literal brace",
+                ));
+            }
+            c => format.push(c),
+        }
+    }
+
+    Ok((format, placeholders))
+}
@@ -0,0 +1,52 @@
+//! Doc comment text of variants, exposed through `DOCS` and `doc`.
+
+use syn::{Attribute, Expr, Lit, Meta};
+
+/// The doc comment written on an item, as rustdoc would flow it: lines are trimmed, the lines
+/// of a paragraph are joined with spaces and paragraphs are separated by a blank line.
+pub(crate) fn text(attrs: &[Attribute]) -> String {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(lit) => Some(lit.value()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|value| {
+            // `split` rather than `lines`, so that an empty `///` still ends a paragraph.
+            value
+                .split('\n')
+                .map(|line| line.trim().to_owned())
+                .collect::<Vec<_>>()
+        });
+
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut open = false;
+    for line in lines {
+        if line.is_empty() {
+            open = false;
+        } else if open {
+            let paragraph = paragraphs.last_mut().expect("an open paragraph exists");
+            paragraph.push(' ');
+            paragraph.push_str(&line);
+        } else {
+            paragraphs.push(line);
+            open = true;
+        }
+    }
+
+    paragraphs.join("\n\n")
+}
+
+/// The first paragraph of a doc comment produced by [`text`].
+pub(crate) fn summary(text: &str) -> Option<&str> {
+    text.split("\n\n")
+        .next()
+        .filter(|summary| !summary.is_empty())
+}
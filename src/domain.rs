@@ -0,0 +1,465 @@
+//! Finite value domains for the fields Enumly can enumerate.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Expr, ExprLit, ExprRange, ExprUnary, Field, Fields, GenericArgument, Lit, Member, Path,
+    PathArguments, RangeLimits, Type, UnOp,
+};
+
+use crate::attr::FieldAttrs;
+
+/// Primitive integer types that can be enumerated with `#[enumly(range = ...)]`.
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// The values of a primitive integer type, or `None` for `usize` and `isize`, whose width
+/// depends on the target. `u128` is cut at `i128::MAX`, past which literals are not parsed.
+fn integer_bounds(name: &str) -> Option<(i128, i128)> {
+    Some(match name {
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" => (0, u64::MAX.into()),
+        "u128" => (0, i128::MAX),
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" => (i64::MIN.into(), i64::MAX.into()),
+        "i128" => (i128::MIN, i128::MAX),
+        _ => return None,
+    })
+}
+
+/// Other payload types that have no finite set of values to enumerate.
+const UNBOUNDED: &[&str] = &["f32", "f64", "char", "str", "String"];
+
+/// The fields of a struct or variant, enumerated as a cartesian product in which the last field
+/// changes fastest.
+pub(crate) struct Product {
+    fields: Vec<(Member, Domain)>,
+}
+
+impl Product {
+    /// Classifies every field, reporting the first one that cannot be enumerated through
+    /// `unsupported`.
+    pub(crate) fn classify(
+        fields: &Fields,
+        unsupported: impl Fn(&Field) -> syn::Error,
+    ) -> syn::Result<Self> {
+        let fields = fields
+            .members()
+            .zip(fields)
+            .map(|(member, field)| match Domain::classify(field)? {
+                Some(domain) => Ok((member, domain)),
+                None => Err(unsupported(field)),
+            })
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self { fields })
+    }
+
+    /// Gives every field a single constant value, for variants marked `default_fields`.
+    pub(crate) fn defaults(fields: &Fields) -> syn::Result<Self> {
+        let fields = fields
+            .members()
+            .zip(fields)
+            .map(|(member, field)| {
+                let attrs = FieldAttrs::parse(&field.attrs)?;
+                if let Some(range) = attrs.range {
+                    return Err(syn::Error::new(
+                        range.span(),
+                        "`range` has no effect on fields of a variant with `default_fields`",
+                    ));
+                }
+                let value = match attrs.default {
+                    Some(value) => value,
+                    None => default_value(&field.ty).ok_or_else(|| {
+                        syn::Error::new(
+                            field.ty.span(),
+                            "no constant default is known for this type; \
+                             add `#[enumly(default = ...)]` to the field",
+                        )
+                    })?,
+                };
+                Ok((member, Domain::Const(Box::new(value))))
+            })
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self { fields })
+    }
+
+    /// Expression for the number of values, the product of the field counts.
+    pub(crate) fn count(&self, krate: &Path) -> TokenStream {
+        multiply(self.fields.iter().map(|(_, domain)| domain.count(krate)))
+    }
+
+    /// Expression building `path` from the combination at `index`, which must be below
+    /// [`Product::count`].
+    pub(crate) fn value_at(
+        &self,
+        path: &TokenStream,
+        index: &TokenStream,
+        krate: &Path,
+    ) -> TokenStream {
+        let counts: Vec<TokenStream> = self
+            .fields
+            .iter()
+            .map(|(_, domain)| domain.count(krate))
+            .collect();
+        let fields = self.fields.iter().enumerate().map(|(i, (member, domain))| {
+            let stride = multiply(counts[i + 1..].iter().cloned());
+            let count = &counts[i];
+            let value = domain.value_at(&quote! { (#index / #stride % #count) });
+            quote! { #member: #value }
+        });
+
+        quote! { #path { #(#fields),* } }
+    }
+
+    /// Pattern matching `path` that binds every field by reference for [`Product::index_of`].
+    pub(crate) fn pattern(&self, path: &TokenStream) -> TokenStream {
+        let fields = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, (member, domain))| match domain {
+                Domain::Const(_) => quote! { #member: _ },
+                _ => {
+                    let binding = binding(i);
+                    quote! { #member: ref #binding }
+                }
+            });
+
+        quote! { #path { #(#fields),* } }
+    }
+
+    /// Expression for the position of the value bound by [`Product::pattern`]. `label` names
+    /// the variant or struct in the panic raised for values outside a `range`.
+    pub(crate) fn index_of(&self, label: &str, krate: &Path) -> TokenStream {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, domain))| !matches!(domain, Domain::Const(_)))
+            .fold(quote! { 0usize }, |acc, (i, (_, domain))| {
+                let count = domain.count(krate);
+                let index = domain.index_of(&binding(i).into_token_stream(), label);
+                quote! { (#acc * #count + #index) }
+            })
+    }
+}
+
+/// Product of `counts`, or `1usize` when there are none.
+fn multiply(counts: impl Iterator<Item = TokenStream>) -> TokenStream {
+    counts
+        .reduce(|acc, count| quote! { #acc * #count })
+        .map_or_else(|| quote! { 1usize }, |product| quote! { (#product) })
+}
+
+/// A constant usable in place of `Default::default()`, which cannot be called in a constant.
+fn default_value(ty: &Type) -> Option<Expr> {
+    match ty {
+        Type::Group(group) => default_value(&group.elem),
+        Type::Paren(paren) => default_value(&paren.elem),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => Some(syn::parse_quote! { () }),
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.path.is_ident("str") => Some(syn::parse_quote! { "" }),
+            _ => None,
+        },
+        Type::Path(path) if path.qself.is_none() => {
+            let last = path.path.segments.last()?;
+            let value = if INTEGERS.iter().any(|name| last.ident == name) {
+                syn::parse_quote! { 0 }
+            } else if last.ident == "f32" || last.ident == "f64" {
+                syn::parse_quote! { 0.0 }
+            } else if last.ident == "bool" {
+                syn::parse_quote! { false }
+            } else if last.ident == "char" {
+                syn::parse_quote! { '\0' }
+            } else if !is_standard(&path.path) {
+                return None;
+            } else if last.ident == "Option" {
+                syn::parse_quote! { ::core::option::Option::None }
+            } else if last.ident == "String" || last.ident == "Vec" {
+                // Through the path as written, so that `alloc::` types stay usable without `std`.
+                syn::parse_quote! { <#ty>::new() }
+            } else {
+                return None;
+            };
+            Some(value)
+        }
+        _ => None,
+    }
+}
+
+/// Whether `path` is a bare name or starts with `std`, `alloc` or `core`, so that a type named
+/// like a standard one is only given its default when it is that type.
+fn is_standard(path: &Path) -> bool {
+    let mut segments = path.segments.iter();
+    match segments.next() {
+        Some(first) if segments.len() > 0 => ["std", "alloc", "core"]
+            .iter()
+            .any(|name| first.ident == name),
+        _ => path.leading_colon.is_none(),
+    }
+}
+
+/// The value of an integer literal bound, possibly negated, or `None` for any other expression.
+fn literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal(expr).map(|value: i128| -value),
+        Expr::Group(group) => literal(&group.expr),
+        Expr::Paren(paren) => literal(&paren.expr),
+        _ => None,
+    }
+}
+
+fn binding(index: usize) -> syn::Ident {
+    format_ident!("field_{}", index)
+}
+
+/// The set of values a field can take, in the order Enumly lists them.
+pub(crate) enum Domain {
+    /// Another type deriving `Enumly`, enumerated through its own `COUNT` and index mapping.
+    Enumly(Type),
+    /// `false`, then `true`.
+    Bool,
+    /// `None`, then `Some` of every inner value.
+    Option(Box<Domain>),
+    /// Every integer from `start` to `end`, both inclusive. Bounds that are not literals are
+    /// checked against `ty` by `check`, a statement evaluated along with the count.
+    Range {
+        ty: Type,
+        start: Box<Expr>,
+        end: Box<Expr>,
+        check: Option<TokenStream>,
+    },
+    /// A single constant, the default of a field in a `default_fields` variant.
+    Const(Box<Expr>),
+}
+
+impl Domain {
+    /// Classifies a field, returning `None` when its values cannot be enumerated.
+    fn classify(field: &Field) -> syn::Result<Option<Self>> {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if let Some(default) = attrs.default {
+            return Err(syn::Error::new(
+                default.span(),
+                "`default` requires `#[enumly(default_fields)]` on the variant",
+            ));
+        }
+
+        match attrs.range {
+            Some(range) => Self::range(&field.ty, range).map(Some),
+            None => Ok(Self::classify_type(&field.ty)),
+        }
+    }
+
+    fn classify_type(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::Group(group) => Self::classify_type(&group.elem),
+            Type::Paren(paren) => Self::classify_type(&paren.elem),
+            Type::Path(path) if path.qself.is_none() => {
+                let last = path.path.segments.last()?;
+                if last.ident == "bool" {
+                    Some(Domain::Bool)
+                } else if last.ident == "Option" {
+                    let PathArguments::AngleBracketed(args) = &last.arguments else {
+                        return None;
+                    };
+                    match args.args.first() {
+                        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => {
+                            Self::classify_type(inner).map(|inner| Domain::Option(Box::new(inner)))
+                        }
+                        _ => None,
+                    }
+                } else if INTEGERS
+                    .iter()
+                    .chain(UNBOUNDED)
+                    .any(|name| last.ident == name)
+                {
+                    None
+                } else if path
+                    .path
+                    .segments
+                    .iter()
+                    .any(|segment| !segment.arguments.is_none())
+                {
+                    // Containers such as `Vec<T>` or `Box<T>`; an Enumly type has no arguments
+                    // since only non-generic enums and structs can be enumerated.
+                    None
+                } else {
+                    Some(Domain::Enumly(ty.clone()))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn range(ty: &Type, range: ExprRange) -> syn::Result<Self> {
+        let integer = match ty {
+            Type::Path(path) => path
+                .path
+                .get_ident()
+                .map(ToString::to_string)
+                .filter(|ident| INTEGERS.contains(&ident.as_str())),
+            _ => None,
+        };
+        let Some(integer) = integer else {
+            return Err(syn::Error::new(
+                ty.span(),
+                "`range` can only be used on primitive integer fields",
+            ));
+        };
+
+        let span = range.span();
+        let (Some(start), Some(end)) = (range.start, range.end) else {
+            return Err(syn::Error::new(
+                range.limits.span(),
+                "`range` needs both a start and an end",
+            ));
+        };
+        let closed = matches!(range.limits, RangeLimits::Closed(_));
+        let bounds = integer_bounds(&integer);
+        let check = match (literal(&start), literal(&end), bounds) {
+            (Some(first), Some(bound), bounds) => {
+                let last = if closed { bound } else { bound - 1 };
+                let message = if last < first {
+                    Some("`range` holds no values; its start must not be past its end".to_owned())
+                } else {
+                    bounds
+                        .filter(|&(min, max)| first < min || last > max)
+                        .map(|_| format!("`range` holds values that do not fit in `{integer}`"))
+                };
+                if let Some(message) = message {
+                    return Err(syn::Error::new_spanned(
+                        ExprRange {
+                            start: Some(start),
+                            end: Some(end),
+                            ..range
+                        },
+                        message,
+                    ));
+                }
+                None
+            }
+            // Every value of these fits in the `i128` the range is computed in, so a bound that
+            // type-checks as `i128` cannot overflow.
+            _ if integer == "i128" || integer == "u128" => None,
+            _ => {
+                let message = format!("Enumly `range` holds values that do not fit in `{integer}`");
+                let last = match closed {
+                    true => quote! { (#end) as i128 },
+                    false => quote! { (#end) as i128 - 1 },
+                };
+                let fits = quote! {
+                    (#start) as i128 >= <#ty>::MIN as i128 && #last <= <#ty>::MAX as i128
+                };
+                Some(quote_spanned! {span=>
+                    const { ::core::assert!(#fits, #message) };
+                })
+            }
+        };
+        let end = match closed {
+            true => end,
+            false => syn::parse_quote! { (#end) - 1 },
+        };
+
+        Ok(Domain::Range {
+            ty: ty.clone(),
+            start,
+            end,
+            check,
+        })
+    }
+
+    /// Expression for the number of values in the domain, read through the `Enumly` trait of
+    /// `krate` for nested Enumly types.
+    pub(crate) fn count(&self, krate: &Path) -> TokenStream {
+        match self {
+            Domain::Enumly(ty) => quote_spanned! {ty.span()=>
+                <#ty as #krate::Enumly>::COUNT
+            },
+            Domain::Bool => quote! { 2usize },
+            Domain::Option(inner) => {
+                let inner = inner.count(krate);
+                quote! { (1usize + #inner) }
+            }
+            Domain::Range {
+                start, end, check, ..
+            } => quote! {
+                {
+                    #check
+                    (((#end) as i128 - (#start) as i128) as usize + 1)
+                }
+            },
+            Domain::Const(_) => quote! { 1usize },
+        }
+    }
+
+    /// Expression for the value at `index`, which must be below [`Domain::count`].
+    pub(crate) fn value_at(&self, index: &TokenStream) -> TokenStream {
+        match self {
+            Domain::Enumly(ty) => quote! {
+                ::core::option::Option::unwrap(<#ty>::from_index(#index))
+            },
+            Domain::Bool => quote! { (#index != 0) },
+            Domain::Option(inner) => {
+                let inner = inner.value_at(&quote! { (#index - 1) });
+                quote! {
+                    if #index == 0 {
+                        ::core::option::Option::None
+                    } else {
+                        ::core::option::Option::Some(#inner)
+                    }
+                }
+            }
+            Domain::Range { ty, start, .. } => quote! {
+                (((#start) as i128 + (#index) as i128) as #ty)
+            },
+            Domain::Const(value) => value.to_token_stream(),
+        }
+    }
+
+    /// Expression for the position of `value`, a reference to a value of the domain. Values
+    /// outside a `range` panic with a message naming `label`.
+    pub(crate) fn index_of(&self, value: &TokenStream, label: &str) -> TokenStream {
+        match self {
+            Domain::Enumly(ty) => quote! { <#ty>::index(#value) },
+            Domain::Bool => quote! { (*#value as usize) },
+            Domain::Option(inner) => {
+                let inner = inner.index_of(&quote! { value }, label);
+                quote! {
+                    match #value {
+                        ::core::option::Option::None => 0usize,
+                        ::core::option::Option::Some(value) => 1 + #inner,
+                    }
+                }
+            }
+            Domain::Range { start, end, .. } => {
+                let message =
+                    format!("`{label}` holds a value outside its Enumly `range` and has no index");
+                quote! {
+                    {
+                        let value = *#value as i128;
+                        let offset = value - (#start) as i128;
+                        if offset < 0 || value > (#end) as i128 {
+                            ::core::panic!(#message);
+                        }
+                        offset as usize
+                    }
+                }
+            }
+            Domain::Const(_) => quote! { 0usize },
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! `FromStr` implementation and its generated error type.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+use syn::ext::IdentExt;
+
+use crate::attr::ContainerAttrs;
+use crate::{Shape, Variant, trait_error_vis};
+
+pub(crate) fn expand(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    variants: &[Variant],
+) -> TokenStream {
+    let name = &input.ident;
+    let vis = trait_error_vis(input);
+    let error = format_ident!("Parse{}Error", name.unraw(), span = name.span());
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let enum_name = name.unraw().to_string();
+    let error_doc =
+        format!("An error returned when parsing a [`{enum_name}`] from a string fails.");
+    // Only unit variants can be built from a name alone; hidden and skipped ones are not parsed.
+    let variants: Vec<&Variant> = variants
+        .iter()
+        .filter(|variant| matches!(variant.shape, Shape::Unit) && variant.is_named())
+        .collect();
+    let expected = variants.iter().map(|variant| &variant.name);
+    let error_value = quote! {
+        ::core::result::Result::Err(#error {
+            input: ::std::string::String::from(s),
+            expected: &[#(#expected),*],
+        })
+    };
+    let body = if container.ascii_case_insensitive {
+        let checks = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let accepted = accepted(variant);
+            quote! {
+                if #(s.eq_ignore_ascii_case(#accepted))||* {
+                    return ::core::result::Result::Ok(Self::#ident);
+                }
+            }
+        });
+        quote! {
+            #(#checks)*
+            #error_value
+        }
+    } else {
+        let arms = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let accepted = accepted(variant);
+            quote! { #(#accepted)|* => ::core::result::Result::Ok(Self::#ident) }
+        });
+        quote! {
+            match s {
+                #(#arms,)*
+                _ => #error_value,
+            }
+        }
+    };
+
+    quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #error {
+            input: ::std::string::String,
+            expected: &'static [&'static str],
+        }
+
+        impl #error {
+            /// Returns the string that failed to parse.
+            #vis fn input(&self) -> &str {
+                &self.input
+            }
+
+            /// Returns the names that would have been accepted.
+            #vis fn expected(&self) -> &'static [&'static str] {
+                self.expected
+            }
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, "unknown {} `{}`; expected one of ", #enum_name, self.input)?;
+                for (i, name) in self.expected.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    ::core::write!(f, "`{}`", name)?;
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        impl ::core::error::Error for #error {}
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = #error;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+    }
+}
+
+/// The name of a variant followed by its aliases.
+fn accepted(variant: &Variant) -> Vec<String> {
+    std::iter::once(variant.name.clone())
+        .chain(variant.aliases.iter().map(|alias| alias.value()))
+        .collect()
+}
@@ -0,0 +1,182 @@
+//! Positional items: `COUNT`, `VARIANTS`, `index` and `from_index`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Path, Visibility};
+
+use crate::Shape;
+
+/// A way of building the type: an enum variant such as `Self::Red`, or `Self` for a struct.
+pub(crate) struct Case<'a> {
+    pub(crate) path: TokenStream,
+    /// How the case is named in panic messages, such as `Color::Red`.
+    pub(crate) label: String,
+    pub(crate) shape: &'a Shape,
+}
+
+/// Names and visibility of the generated positional constants.
+pub(crate) struct Items<'a> {
+    pub(crate) vis: &'a Visibility,
+    /// Path of the `enumly` facade, whose trait gives the counts of nested Enumly types.
+    pub(crate) krate: &'a Path,
+    pub(crate) count: Ident,
+    pub(crate) variants: Ident,
+}
+
+pub(crate) fn expand(items: &Items, cases: &[Case]) -> TokenStream {
+    let (skipped, listed): (Vec<&Case>, Vec<&Case>) = cases
+        .iter()
+        .partition(|case| matches!(case.shape, Shape::Skipped));
+    let skipped_arms: Vec<TokenStream> = skipped
+        .iter()
+        .map(|case| {
+            let path = &case.path;
+            let message = format!("`{}` is skipped by Enumly and has no index", case.label);
+            quote! { #path { .. } => ::core::panic!(#message) }
+        })
+        .collect();
+
+    if listed.iter().all(|case| matches!(case.shape, Shape::Unit)) {
+        expand_unit(items, &listed, &skipped_arms)
+    } else {
+        expand_fields(items, &listed, &skipped_arms)
+    }
+}
+
+/// Every case is a unit, so positions are known while expanding.
+fn expand_unit(items: &Items, cases: &[&Case], skipped_arms: &[TokenStream]) -> TokenStream {
+    let Items {
+        vis,
+        count: count_ident,
+        variants: variants_ident,
+        ..
+    } = items;
+    let count = cases.len();
+    let variant_exprs = cases.iter().map(|case| &case.path);
+    let index_arms = cases.iter().enumerate().map(|(index, case)| {
+        let path = &case.path;
+        quote! { #path => #index }
+    });
+    let from_index_arms = cases.iter().enumerate().map(|(index, case)| {
+        let path = &case.path;
+        quote! { #index => ::core::option::Option::Some(#path) }
+    });
+
+    quote! {
+        #vis const #count_ident: usize = #count;
+        #vis const #variants_ident: &'static [Self] = &[#(#variant_exprs),*];
+
+        #vis const fn index(&self) -> usize {
+            match *self {
+                #(#index_arms,)*
+                #(#skipped_arms,)*
+            }
+        }
+
+        #vis const fn from_index(index: usize) -> ::core::option::Option<Self> {
+            match index {
+                #(#from_index_arms,)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    }
+}
+
+/// Some cases enumerate their fields, so positions are computed from the field counts at
+/// compile time and `VARIANTS` is filled in by walking `from_index`.
+fn expand_fields(items: &Items, cases: &[&Case], skipped_arms: &[TokenStream]) -> TokenStream {
+    let Items {
+        vis,
+        krate,
+        count: count_ident,
+        variants: variants_ident,
+    } = items;
+    let counts: Vec<TokenStream> = cases
+        .iter()
+        .map(|case| match case.shape {
+            Shape::Fields(product) => product.count(krate),
+            _ => quote! { 1usize },
+        })
+        .collect();
+    let offsets: Vec<TokenStream> = (0..cases.len())
+        .map(|i| match &counts[..i] {
+            [] => quote! { 0usize },
+            previous => quote! { #(#previous)+* },
+        })
+        .collect();
+
+    let index_arms = cases.iter().zip(&offsets).map(|(case, offset)| {
+        let path = &case.path;
+        match case.shape {
+            Shape::Fields(product) => {
+                let pattern = product.pattern(path);
+                let index_of = product.index_of(&case.label, krate);
+                quote! { #pattern => #offset + #index_of }
+            }
+            _ => quote! { #path => #offset },
+        }
+    });
+
+    let rest = quote! { rest };
+    let steps = cases
+        .iter()
+        .zip(&counts)
+        .enumerate()
+        .map(|(i, (case, count))| {
+            let path = &case.path;
+            let found = match case.shape {
+                Shape::Fields(product) => {
+                    let value = product.value_at(path, &rest, krate);
+                    quote! {
+                        if #rest < #count {
+                            return ::core::option::Option::Some(#value);
+                        }
+                    }
+                }
+                _ => quote! {
+                    if #rest == 0 {
+                        return ::core::option::Option::Some(#path);
+                    }
+                },
+            };
+            let advance = (i + 1 < cases.len()).then(|| quote! { #rest -= #count; });
+            quote! { #found #advance }
+        });
+
+    let mutability = (cases.len() > 1).then(|| quote! { mut });
+    let seed = match cases.iter().find(|case| matches!(case.shape, Shape::Unit)) {
+        Some(case) => case.path.clone(),
+        None => quote! {
+            ::core::option::Option::unwrap(Self::from_index(0))
+        },
+    };
+    quote! {
+        #vis const #count_ident: usize = #(#counts)+*;
+        // Most payloads have no destructor, but whether one does is unknown while expanding.
+        #[allow(clippy::forget_non_drop)]
+        #vis const #variants_ident: &'static [Self] = &{
+            let mut variants = [const { #seed }; Self::#count_ident];
+            let mut index = 0;
+            while index < Self::#count_ident {
+                let variant = ::core::option::Option::unwrap(Self::from_index(index));
+                // Destructors cannot run in a constant, so the replaced seed is forgotten.
+                ::core::mem::forget(::core::mem::replace(&mut variants[index], variant));
+                index += 1;
+            }
+            variants
+        };
+
+        #vis const fn index(&self) -> usize {
+            match *self {
+                #(#index_arms,)*
+                #(#skipped_arms,)*
+            }
+        }
+
+        #vis const fn from_index(index: usize) -> ::core::option::Option<Self> {
+            let #mutability #rest = index;
+            #(#steps)*
+            ::core::option::Option::None
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Named iterator type over every value, returned by `iter()`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Ident, Path, Visibility};
+
+/// Expands to the iterator type, `{Name}Iter` unless `#[enumly(iter = ...)]` names it, and the
+/// `iter` constructor on the enum or struct.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    vis: &Visibility,
+    krate: &Path,
+    iter: Option<&Ident>,
+) -> TokenStream {
+    let name = &input.ident;
+    let iter = match iter {
+        Some(iter) => iter.clone(),
+        None => format_ident!("{}Iter", name.unraw(), span = name.span()),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let item = quote! { #name #ty_generics };
+
+    let iter_doc = format!(
+        "An iterator over every [`{}`] value in index order, created by `{}::iter`.",
+        name.unraw(),
+        name.unraw()
+    );
+    let iter_name = iter.to_string();
+
+    quote! {
+        #[doc = #iter_doc]
+        #vis struct #iter #impl_generics #where_clause {
+            front: usize,
+            back: usize,
+            marker: ::core::marker::PhantomData<fn() -> #item>,
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns an iterator yielding every value by value, in index order.
+            #vis const fn iter() -> #iter #ty_generics {
+                #iter {
+                    front: 0,
+                    back: <Self as #krate::Enumly>::COUNT,
+                    marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics ::core::iter::Iterator for #iter #ty_generics #where_clause {
+            type Item = #item;
+
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                if self.front == self.back {
+                    return ::core::option::Option::None;
+                }
+                self.front += 1;
+                <#item>::from_index(self.front - 1)
+            }
+
+            fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                let len = self.back - self.front;
+                (len, ::core::option::Option::Some(len))
+            }
+
+            fn nth(&mut self, n: usize) -> ::core::option::Option<Self::Item> {
+                self.front = ::core::cmp::min(self.front.saturating_add(n), self.back);
+                self.next()
+            }
+        }
+
+        impl #impl_generics ::core::iter::DoubleEndedIterator for #iter #ty_generics #where_clause {
+            fn next_back(&mut self) -> ::core::option::Option<Self::Item> {
+                if self.front == self.back {
+                    return ::core::option::Option::None;
+                }
+                self.back -= 1;
+                <#item>::from_index(self.back)
+            }
+        }
+
+        impl #impl_generics ::core::iter::ExactSizeIterator for #iter #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::iter::FusedIterator for #iter #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::clone::Clone for #iter #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    front: self.front,
+                    back: self.back,
+                    marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Debug for #iter #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(#iter_name)
+                    .field("front", &self.front)
+                    .field("back", &self.back)
+                    .finish()
+            }
+        }
+    }
+}
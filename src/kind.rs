@@ -0,0 +1,95 @@
+//! Fieldless sibling enum generated for `#[enumly(kind = Name)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::ext::IdentExt;
+use syn::{Attribute, DeriveInput, Ident, Visibility};
+
+use crate::attr::ContainerAttrs;
+use crate::{Shape, Variant};
+
+/// Expands to the kind enum, which derives `Enumly` itself, and a `kind` method on the
+/// original enum mapping every variant to its fieldless counterpart.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    vis: &Visibility,
+    kind: &Ident,
+    variants: &[Variant],
+) -> TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let kind_doc = format!("The fieldless kind of each [`{}`] variant.", name.unraw());
+    let case_insensitive = container
+        .ascii_case_insensitive
+        .then(|| quote! { #[enumly(ascii_case_insensitive)] });
+    let from_str = container
+        .from_str
+        .as_ref()
+        .map(|path| quote! { #[enumly(#path)] });
+    let krate = container.krate();
+    // The kind enum is expanded separately, so it needs the same facade path.
+    let krate_attr = container.krate.as_ref().map(|path| {
+        let path = path.to_token_stream().to_string();
+        quote! { #[enumly(crate = #path)] }
+    });
+    // The kind enum has the same variants, so it needs the same replacement item names.
+    let item_names = [
+        ("count", &container.count),
+        ("variants", &container.variants),
+        ("names", &container.names),
+        ("docs", &container.docs),
+    ]
+    .into_iter()
+    .filter_map(|(option, ident)| {
+        let option = Ident::new(option, Span::call_site());
+        let value = ident.as_ref()?.to_string();
+        Some(quote! { #[enumly(#option = #value)] })
+    });
+    let kind_variants = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+        let aliases = &variant.aliases;
+        let docs = variant.attrs.iter().filter(|attr| is_doc(attr));
+        let listing = match variant.shape {
+            Shape::Skipped => Some(quote! { #[enumly(skip)] }),
+            _ if variant.hidden => Some(quote! { #[enumly(hidden)] }),
+            _ => None,
+        };
+        quote! {
+            #(#docs)*
+            #[enumly(rename = #name #(, alias = #aliases)*)]
+            #listing
+            #ident
+        }
+    });
+    let kind_arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        quote! { Self::#ident { .. } => #kind::#ident }
+    });
+
+    quote! {
+        #[doc = #kind_doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, #krate::Enumly)]
+        #krate_attr
+        #case_insensitive
+        #from_str
+        #(#item_names)*
+        #vis enum #kind {
+            #(#kind_variants,)*
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis const fn kind(&self) -> #kind {
+                match *self {
+                    #(#kind_arms,)*
+                }
+            }
+        }
+    }
+}
+
+fn is_doc(attr: &Attribute) -> bool {
+    attr.path().is_ident("doc")
+}
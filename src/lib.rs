@@ -1,15 +1,40 @@
 #![doc = "Provides a procedural macro that exposes a compile-time static list of all variants of an enum."]
 
+mod attr;
+mod case;
+mod clap;
+mod discriminant;
+mod display;
+mod doc;
+mod domain;
+mod from_str;
+mod index;
+mod iter;
+mod kind;
+mod map;
+mod ordinal;
+mod serde;
+mod set;
+
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
-use syn::{Attribute, Data, DeriveInput, Fields, parse_macro_input};
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, Ident, LitStr, Path,
+    Visibility, parse_macro_input,
+};
+
+use crate::attr::{ContainerAttrs, Target, VariantAttrs};
+use crate::domain::Product;
+use crate::index::{Case, Items};
 
 /// Derive macro that exposes compile-time constants for the full set of enum variants.
 ///
 /// ---
 /// # Examples
-/// ```ignore
+/// ```
 /// use enumly::Enumly;
 ///
 /// #[derive(Enumly, Debug, PartialEq)]
@@ -21,10 +46,588 @@ use syn::{Attribute, Data, DeriveInput, Fields, parse_macro_input};
 ///
 /// assert_eq!(Color::COUNT, 3);
 /// assert_eq!(Color::VARIANTS, &[Color::Red, Color::Green, Color::Blue]);
+/// assert_eq!(Color::NAMES, &["Red", "Green", "Blue"]);
+/// assert_eq!(Color::Green.as_str(), "Green");
+/// assert_eq!(Color::Blue.index(), 2);
+/// assert_eq!(Color::from_index(1), Some(Color::Green));
+/// assert_eq!(Color::from_index(3), None);
+/// ```
+///
+/// ---
+/// The derive also implements the `enumly::Enumly` trait, which mirrors `COUNT`, `VARIANTS`,
+/// `index` and `from_index` so generic code can be written over any Enumly enum. The inherent
+/// items stay in place and remain usable in `const` contexts:
+/// ```
+/// use enumly::Enumly;
+///
+/// fn all<E: Enumly>() -> &'static [E] {
+///     E::VARIANTS
+/// }
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+/// }
+///
+/// assert_eq!(all::<Color>(), Color::VARIANTS);
+/// assert_eq!(<Color as Enumly>::COUNT, 2);
+/// ```
+///
+/// The generated code refers to the trait as `::enumly::Enumly`. `#[enumly(crate = "...")]`
+/// names another path to the `enumly` crate, for example when the dependency is renamed or
+/// re-exported by another crate:
+/// ```
+/// mod toolkit {
+///     pub use enumly;
+/// }
+///
+/// use toolkit::enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// #[enumly(crate = "toolkit::enumly")]
+/// enum Side {
+///     Left,
+///     Right,
+/// }
+///
+/// assert_eq!(<Side as Enumly>::COUNT, 2);
+/// ```
+///
+/// ---
+/// `iter()` returns a `{Name}Iter` that yields every value by value in index order, so no
+/// `Copy` bound is needed. It is double-ended, exact-size and fused, and can be stored in
+/// structs. `#[enumly(iter = Name)]` picks another name for the type, for example when
+/// another derive already generates `{Name}Iter`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// struct Palette {
+///     colors: ColorIter,
+/// }
+///
+/// let mut palette = Palette { colors: Color::iter() };
+/// assert_eq!(palette.colors.len(), 3);
+/// assert_eq!(palette.colors.next_back(), Some(Color::Blue));
+/// assert_eq!(palette.colors.collect::<Vec<_>>(), [Color::Red, Color::Green]);
+/// ```
+///
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(iter = Shades)]
+/// enum Shade {
+///     Light,
+///     Dark,
+/// }
+///
+/// let shades: Shades = Shade::iter();
+/// assert_eq!(shades.rev().next(), Some(Shade::Dark));
+/// ```
+///
+/// ---
+/// Const methods move between neighbouring values in index order:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Mode {
+///     Off,
+///     Low,
+///     High,
+/// }
+///
+/// assert_eq!(Mode::Off.next(), Some(Mode::Low));
+/// assert_eq!(Mode::Off.prev(), None);
+/// assert_eq!(Mode::High.next_wrapping(), Mode::Off);
+/// assert_eq!(Mode::Off.prev_wrapping(), Mode::High);
+/// assert_eq!(Mode::High.checked_offset(-2), Some(Mode::Off));
+/// assert_eq!(Mode::High.distance(&Mode::Off), -2);
+/// assert!(Mode::Off.is_first() && Mode::High.is_last());
+/// ```
+///
+/// ---
+/// Discriminants follow `#[repr(...)]` (or `isize` without one), including explicit `= N`
+/// values and the implicit increments after them:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop,
+///     Load = 0x10,
+///     Store,
+///     Halt = 0xff,
+/// }
+///
+/// assert_eq!(Opcode::DISCRIMINANTS, &[0x00, 0x10, 0x11, 0xff]);
+/// assert_eq!(Opcode::Store.to_discriminant(), 0x11);
+/// assert_eq!(Opcode::from_discriminant(0xff), Some(Opcode::Halt));
+/// assert_eq!(Opcode::from_discriminant(0x12), None);
+/// ```
+///
+/// ---
+/// `#[enumly(repr_conversions)]` additionally implements `TryFrom<repr>` for the enum and
+/// `From<Enum>` for the repr. Values that match no variant are rejected with a generated
+/// `TryFrom{Enum}Error`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(repr_conversions)]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Nop,
+///     Halt = 0xff,
+/// }
+///
+/// assert_eq!(Opcode::try_from(0xff), Ok(Opcode::Halt));
+/// assert_eq!(Opcode::try_from(0x01).unwrap_err().value(), 0x01);
+/// assert_eq!(u8::from(Opcode::Halt), 0xff);
+/// ```
+///
+/// ---
+/// Fails to compile when `repr_conversions` is requested without an integer `repr`:
+/// ```compile_fail
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// #[enumly(repr_conversions)]
+/// enum Bad {
+///     A,
+///     B,
+/// }
+/// ```
+///
+/// ---
+/// `#[enumly(from_str)]` implements `FromStr` using the variant names. Failures are reported with
+/// a generated `Parse{Enum}Error` that carries the rejected input and the accepted names. The
+/// input is kept as a `std::string::String`, so `from_str`, and `serde = "name"` which relies on
+/// it, need `std`; the rest of the expansion only refers to `core`.
+///
+/// Names can be customised with `#[enumly(rename_all = "...")]` on the enum and
+/// `#[enumly(rename = "...")]` on individual variants. Supported rules are `lowercase`,
+/// `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`,
+/// `kebab-case` and `SCREAMING-KEBAB-CASE`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(from_str, rename_all = "kebab-case")]
+/// enum Shade {
+///     LightGray,
+///     DarkGray,
+///     #[enumly(rename = "ink")]
+///     Black,
+/// }
+///
+/// assert_eq!(Shade::NAMES, &["light-gray", "dark-gray", "ink"]);
+/// assert_eq!(Shade::Black.as_str(), "ink");
+/// assert_eq!("dark-gray".parse::<Shade>(), Ok(Shade::DarkGray));
+/// assert!("Black".parse::<Shade>().is_err());
+/// ```
+///
+/// ---
+/// Extra spellings are accepted with `#[enumly(alias = "...")]`, which may be repeated, and
+/// `#[enumly(ascii_case_insensitive)]` on the enum ignores ASCII case while parsing:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(from_str, rename_all = "lowercase", ascii_case_insensitive)]
+/// enum Color {
+///     #[enumly(alias = "r")]
+///     Red,
+///     #[enumly(alias = "g")]
+///     Green,
+/// }
+///
+/// assert_eq!("RED".parse::<Color>(), Ok(Color::Red));
+/// assert_eq!("G".parse::<Color>(), Ok(Color::Green));
+/// ```
+///
+/// ---
+/// `#[enumly(display)]` implements `Display`, writing the name. A `#[enumly(display = "...")]`
+/// template on the enum or on a variant also implements it and changes the output; it may refer
+/// to `{name}`, `{index}` and `{discriminant}`, and `{{`/`}}` write literal braces:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// #[repr(u16)]
+/// #[enumly(display = "{name} ({discriminant})")]
+/// enum Status {
+///     Ok = 200,
+///     NotFound = 404,
+///     #[enumly(display = "{{{name}}}")]
+///     Teapot = 418,
+/// }
+///
+/// assert_eq!(Status::NotFound.to_string(), "NotFound (404)");
+/// assert_eq!(Status::Teapot.to_string(), "{Teapot}");
+/// ```
+///
+/// ---
+/// With the `serde` feature, `#[enumly(serde = "...")]` implements `Serialize` and
+/// `Deserialize`. `"name"` writes the name and reads it back through `FromStr`, so it needs
+/// `from_str`; renames and aliases apply, and it cannot be combined with hidden variants;
+/// `"index"` writes the position in `VARIANTS` as a `u64`, and also supports
+/// flattened variants; `"discriminant"` writes the value of `to_discriminant`. Serializing a
+/// skipped variant fails:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(serde = "name", from_str, rename_all = "snake_case")]
+/// enum Region {
+///     #[enumly(alias = "eu")]
+///     EuWest,
+///     UsEast,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Region::UsEast).unwrap(), r#""us_east""#);
+/// assert_eq!(serde_json::from_str::<Region>(r#""eu""#).unwrap(), Region::EuWest);
+/// ```
+///
+/// ---
+/// With the `clap` feature, `#[enumly(clap)]` implements `clap::ValueEnum` from the variant
+/// names, so the command line accepts exactly what `from_str` accepts: aliases are
+/// accepted, while hidden and skipped variants are not. Clap cannot ignore case on behalf of
+/// the type, so `ascii_case_insensitive` is rejected; set `ignore_case` on the argument instead.
+/// The first paragraph of a variant's doc comment becomes its help.
+/// The enum must also implement `Clone`:
+/// ```
+/// use clap::Parser;
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+/// #[enumly(clap, rename_all = "kebab-case")]
+/// enum Format {
+///     /// Human readable output.
+///     PlainText,
+///     #[enumly(alias = "j")]
+///     Json,
+///     #[enumly(hidden)]
+///     Legacy,
+/// }
+///
+/// #[derive(Parser)]
+/// struct Cli {
+///     #[arg(long, value_enum)]
+///     format: Format,
+/// }
+///
+/// let cli = Cli::parse_from(["app", "--format", "plain-text"]);
+/// assert_eq!(cli.format, Format::PlainText);
+/// assert!(Cli::try_parse_from(["app", "--format", "legacy"]).is_err());
+/// ```
+///
+/// ---
+/// `DOCS` lists the doc comment of every variant alongside `NAMES`, and `doc` returns the one
+/// of a single variant, so help screens can share their text with rustdoc. Lines are trimmed,
+/// the lines of a paragraph are joined with spaces and paragraphs are separated by a blank
+/// line; undocumented variants have an empty string. `#[enumly(docs = "...")]` renames `DOCS`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// enum Mode {
+///     /// Read the file
+///     /// without changing it.
+///     Read,
+///     /// Overwrite the file.
+///     ///
+///     /// Existing contents are lost.
+///     Write,
+///     Append,
+/// }
+///
+/// assert_eq!(
+///     Mode::DOCS,
+///     &[
+///         "Read the file without changing it.",
+///         "Overwrite the file.\n\nExisting contents are lost.",
+///         "",
+///     ]
+/// );
+/// assert_eq!(Mode::Read.doc(), "Read the file without changing it.");
+/// ```
+///
+/// ---
+/// `#[enumly(map = Name)]` generates `Name<V>`, a map holding exactly one `V` per variant in
+/// an inline `[V; COUNT]` array. It supports `Index`/`IndexMut` by key, `from_fn`, `iter`
+/// yielding `(key, &value)` pairs, and `map`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+/// #[enumly(map = ColorMap)]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+/// }
+///
+/// let mut hits = ColorMap::from_fn(|_| 0u32);
+/// hits[Color::Green] += 1;
+/// assert_eq!(hits[Color::Green], 1);
+///
+/// let labels = hits.map(|color, count| format!("{}={count}", color.as_str()));
+/// assert_eq!(labels.iter().next(), Some((Color::Red, &"Red=0".to_string())));
+/// ```
+///
+/// ---
+/// `#[enumly(set = Name)]` generates `Name`, a `Copy` bitset over the variants backed by the
+/// smallest unsigned integer that fits `COUNT` (or an array of `u64` words past 128 variants).
+/// Most operations are `const fn`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+/// #[enumly(set = FeatureSet)]
+/// enum Feature {
+///     Logging,
+///     Metrics,
+///     Tracing,
+/// }
+///
+/// const DEFAULTS: FeatureSet = FeatureSet::from_slice(&[Feature::Logging, Feature::Metrics]);
+///
+/// let mut enabled = DEFAULTS;
+/// enabled.remove(Feature::Metrics);
+/// assert!(enabled.contains(&Feature::Logging));
+/// assert_eq!((!enabled).iter().collect::<Vec<_>>(), [Feature::Metrics, Feature::Tracing]);
+/// assert_eq!((enabled | DEFAULTS).len(), 2);
+/// ```
+///
+/// ---
+/// Fails to compile when two variants would accept the same string:
+/// ```compile_fail
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// enum Bad {
+///     #[enumly(alias = "b")]
+///     Blue,
+///     #[enumly(alias = "b")]
+///     Black,
+/// }
+/// ```
+///
+/// ---
+/// Variants whose fields are all finite are flattened: `VARIANTS` lists the variant once for
+/// every combination of field values, with the last field changing fastest, and `COUNT` is
+/// computed at compile time. Finite fields are other Enumly types, `bool`, `Option<_>` of a
+/// finite type, and integers annotated with `#[enumly(range = start..=end)]` whose bounds fit in
+/// the integer type. Every value of a flattened variant shares its name and doc, so `NAMES` and
+/// `DOCS` repeat them to stay index-aligned with `VARIANTS`. Only unit variants can be parsed, a generated `set` formats
+/// its members by index, and discriminant items are only generated for enums without flattened
+/// variants:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Digit {
+///     Zero,
+///     One,
+/// }
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Key {
+///     Digit(Digit),
+///     Space,
+/// }
+///
+/// assert_eq!(Key::COUNT, 3);
+/// assert_eq!(Key::VARIANTS, &[Key::Digit(Digit::Zero), Key::Digit(Digit::One), Key::Space]);
+/// assert_eq!(Key::Space.index(), 2);
+/// assert_eq!(Key::NAMES, &["Digit", "Digit", "Space"]);
+/// assert_eq!(Key::Space.as_str(), "Space");
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Status {
+///     Blink(bool),
+///     Level(#[enumly(range = 0..=2)] u8),
+/// }
+///
+/// assert_eq!(Status::COUNT, 5);
+/// assert_eq!(Status::VARIANTS[2], Status::Level(0));
+/// assert_eq!(Status::Level(2).index(), 4);
+/// ```
+///
+/// ---
+/// Structs whose fields are all finite derive `Enumly` as the cartesian product of their fields,
+/// in the same order as flattened variants. They get `COUNT`, `VARIANTS`, `index`, `from_index`,
+/// `map` and `set`, but no names, parsing or discriminants:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Suit {
+///     Hearts,
+///     Spades,
+/// }
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// struct Card {
+///     suit: Suit,
+///     #[enumly(range = 1..=13)]
+///     rank: u8,
+/// }
+///
+/// assert_eq!(Card::COUNT, 26);
+/// assert_eq!(Card::VARIANTS[1], Card { suit: Suit::Hearts, rank: 2 });
+/// assert_eq!(Card { suit: Suit::Spades, rank: 1 }.index(), 13);
 /// ```
 ///
 /// ---
-/// Fails to compile when any variant is not unit:
+/// A variant marked `#[enumly(default_fields)]` is listed once, holding a default value for
+/// every field. `VARIANTS` is a constant, so `Default::default()` cannot be called; integers,
+/// floats, `bool`, `char`, `&str`, `String`, `Vec<_>`, `Option<_>` and `()` get their usual
+/// default when named bare or through `std`, `alloc` or `core`, and any other field needs a
+/// constant given with `#[enumly(default = expr)]`:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Size {
+///     Small,
+///     Large,
+/// }
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// enum Weight {
+///     Light,
+///     #[enumly(default_fields)]
+///     Custom {
+///         grams: u32,
+///         label: std::string::String,
+///         #[enumly(default = Size::Large)]
+///         size: Size,
+///     },
+/// }
+///
+/// assert_eq!(Weight::COUNT, 2);
+/// assert_eq!(
+///     Weight::VARIANTS[1],
+///     Weight::Custom { grams: 0, label: String::new(), size: Size::Large }
+/// );
+/// let custom = Weight::Custom { grams: 500, label: "sack".into(), size: Size::Small };
+/// assert_eq!(custom.index(), 1);
+/// ```
+///
+/// ---
+/// `#[enumly(skip)]` leaves a variant out of `COUNT`, `VARIANTS`, `NAMES`, `DOCS`,
+/// `DISCRIMINANTS` and parsing; its fields are never inspected, but the enum cannot be generic.
+/// Calling `index` on a skipped variant panics. `#[enumly(hidden)]` keeps a variant in
+/// `VARIANTS`, and so in `NAMES` and `DOCS`, but leaves it out of parsing, the names listed by
+/// `Parse{Enum}Error` and the values offered to clap:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(from_str)]
+/// enum Level {
+///     Low,
+///     #[enumly(hidden)]
+///     Legacy,
+///     High,
+///     #[enumly(skip)]
+///     Unknown(String),
+/// }
+///
+/// assert_eq!(Level::COUNT, 3);
+/// assert_eq!(Level::VARIANTS, &[Level::Low, Level::Legacy, Level::High]);
+/// assert_eq!(Level::NAMES, &["Low", "Legacy", "High"]);
+/// let err = "Legacy".parse::<Level>().unwrap_err();
+/// assert_eq!(err.expected(), &["Low", "High"]);
+/// ```
+///
+/// ---
+/// Generated items and types share the visibility of the enum or struct, so a private enum does
+/// not leak a public API. `#[enumly(vis = "...")]` overrides it for inherent items and
+/// standalone types; error types used by trait impls on the enum keep the enum's visibility:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// #[enumly(vis = "pub(crate)", from_str, set = TierSet)]
+/// pub enum Tier {
+///     Free,
+///     Paid,
+/// }
+///
+/// // `Tier::VARIANTS` and `TierSet` are only visible within this crate, while the `Err`
+/// // type of `Tier: FromStr` stays public.
+/// assert_eq!(TierSet::all().len(), Tier::COUNT);
+/// let parsed: Result<Tier, ParseTierError> = "Gold".parse();
+/// assert!(parsed.is_err());
+/// ```
+///
+/// ---
+/// `#[enumly(count = "...", variants = "...", names = "...")]` renames the generated constants,
+/// for example when a variant is itself called `COUNT`. The `Enumly` trait keeps its own names:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly, Debug, PartialEq)]
+/// #[enumly(count = "LEN", variants = "ALL")]
+/// enum Field {
+///     COUNT,
+///     VARIANTS,
+/// }
+///
+/// assert_eq!(Field::LEN, 2);
+/// assert_eq!(Field::ALL, &[Field::COUNT, Field::VARIANTS]);
+/// assert_eq!(<Field as Enumly>::COUNT, 2);
+/// ```
+///
+/// ---
+/// Fails to compile when a variant is named like a generated item:
+/// ```compile_fail
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// enum Bad {
+///     COUNT,
+/// }
+/// ```
+///
+/// ---
+/// Enums whose variants carry data can still be enumerated by kind. `#[enumly(kind = Name)]`
+/// generates a fieldless `Name` enum that derives `Enumly` itself (keeping the variant names and
+/// aliases), plus a `kind(&self) -> Name` method on the original enum. With `kind`, variants with
+/// fields are never flattened, so the original enum only receives the full set of items, and
+/// accepts options that build on them such as `map`, `set` or `display`, when every variant is a
+/// unit variant:
+/// ```
+/// use enumly::Enumly;
+///
+/// #[derive(Enumly)]
+/// #[enumly(kind = MessageKind, rename_all = "snake_case")]
+/// enum Message {
+///     Text(String),
+///     Move { x: i32, y: i32 },
+///     Quit,
+/// }
+///
+/// assert_eq!(MessageKind::COUNT, 3);
+/// assert_eq!(MessageKind::NAMES, &["text", "move", "quit"]);
+/// assert_eq!(Message::Move { x: 1, y: 2 }.kind(), MessageKind::Move);
+/// ```
+///
+/// ---
+/// Fails to compile when a variant or struct has fields that cannot be enumerated and no `kind`
+/// is requested:
 /// ```compile_fail
 /// use enumly::Enumly;
 ///
@@ -35,58 +638,603 @@ use syn::{Attribute, Data, DeriveInput, Fields, parse_macro_input};
 /// }
 /// ```
 ///
-#[proc_macro_derive(Enumly)]
+#[proc_macro_derive(Enumly, attributes(enumly))]
 pub fn derive_enumly(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     if let Some(err) = non_exhaustive_error(&input.attrs) {
-        return err.to_compile_error().into();
+        return Err(err);
     }
 
-    let data_enum = match input.data {
-        Data::Enum(data_enum) => data_enum,
-        _ => {
-            return syn::Error::new(input.ident.span(), "Enumly can only be derived for enums")
-                .to_compile_error()
-                .into();
-        }
-    };
+    match &input.data {
+        Data::Enum(data_enum) => expand_enum(&input, data_enum),
+        Data::Struct(data_struct) => expand_struct(&input, data_struct),
+        Data::Union(_) => Err(syn::Error::new(
+            input.ident.span(),
+            "Enumly can only be derived for enums and structs",
+        )),
+    }
+}
 
-    let mut variant_idents = Vec::with_capacity(data_enum.variants.len());
+fn expand_enum(input: &DeriveInput, data_enum: &DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let container = ContainerAttrs::parse(&input.attrs, Target::Enum)?;
+    let vis = container.vis.clone().unwrap_or_else(|| input.vis.clone());
+    let krate = container.krate();
+    let repr = discriminant::repr(&input.attrs)?;
+    let mut variants = Vec::with_capacity(data_enum.variants.len());
 
-    for variant in data_enum.variants {
+    for variant in &data_enum.variants {
         if let Some(err) = non_exhaustive_error(&variant.attrs) {
-            return err.to_compile_error().into();
+            return Err(err);
         }
 
-        match variant.fields {
-            Fields::Unit => variant_idents.push(variant.ident),
-            _ => {
-                return syn::Error::new(
-                    variant.ident.span(),
-                    "Enumly only supports unit variants; tuple and struct variants are not allowed",
-                )
-                .to_compile_error()
-                .into();
+        let attrs = VariantAttrs::parse(&variant.attrs)?;
+        if attrs.skip.is_some() {
+            let ignored = [
+                ("hidden", &attrs.hidden),
+                ("default_fields", &attrs.default_fields),
+            ];
+            if let Some((option, Some(path))) = ignored.into_iter().find(|(_, path)| path.is_some())
+            {
+                return Err(syn::Error::new(
+                    path.span(),
+                    format!("`{option}` has no effect on skipped variants"),
+                ));
             }
         }
+        let shape = match (&variant.fields, &attrs.default_fields) {
+            _ if attrs.skip.is_some() => Shape::Skipped,
+            (Fields::Unit, Some(path)) => {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "`default_fields` has no effect on unit variants",
+                ));
+            }
+            (Fields::Unit, None) => Shape::Unit,
+            (fields, Some(_)) => Shape::Fields(Product::defaults(fields)?),
+            _ if container.kind.is_some() => Shape::Opaque,
+            (fields, None) => Shape::Fields(Product::classify(fields, unsupported_field)?),
+        };
+
+        let name_span = match &attrs.rename {
+            Some(rename) => rename.span(),
+            None => variant.ident.span(),
+        };
+        let name = match (attrs.rename, container.rename_all) {
+            (Some(rename), _) => rename.value(),
+            (None, Some(rule)) => rule.apply(&variant.ident.unraw().to_string()),
+            (None, None) => variant.ident.unraw().to_string(),
+        };
+
+        variants.push(Variant {
+            ident: variant.ident.clone(),
+            name,
+            name_span,
+            aliases: attrs.aliases,
+            discriminant: variant.discriminant.as_ref().map(|(_, expr)| expr.clone()),
+            attrs: variant.attrs.clone(),
+            hidden: attrs.hidden.is_some(),
+            display: attrs.display,
+            doc: doc::text(&variant.attrs),
+            shape,
+        });
     }
 
-    let name = &input.ident;
-    let count = variant_idents.len();
-    let variant_exprs = variant_idents
+    check_collisions(&variants, container.ascii_case_insensitive)?;
+
+    let kind = container
+        .kind
+        .as_ref()
+        .map(|kind| kind::expand(input, &container, &vis, kind, &variants));
+
+    let mut items = Vec::new();
+    if container.kind.is_some() {
+        items.push((item("kind"), None));
+    }
+
+    if variants
         .iter()
-        .map(|variant| quote! { Self::#variant });
+        .any(|variant| matches!(variant.shape, Shape::Opaque))
+    {
+        check_item_collisions(&variants, &items)?;
+        let enumerable_only = [
+            container.map.as_ref().map(|map| ("map", map.span())),
+            container.set.as_ref().map(|set| ("set", set.span())),
+            container
+                .serde
+                .as_ref()
+                .map(|serde| ("serde", serde.span())),
+            container.clap.as_ref().map(|clap| ("clap", clap.span())),
+            container.iter.as_ref().map(|iter| ("iter", iter.span())),
+            container
+                .display
+                .as_ref()
+                .map(|(path, _)| ("display", path.span())),
+            variants
+                .iter()
+                .find_map(|variant| variant.display.as_ref())
+                .map(|display| ("display", display.span())),
+            container
+                .repr_conversions
+                .as_ref()
+                .map(|path| ("repr_conversions", path.span())),
+        ];
+        if let Some((option, span)) = enumerable_only.into_iter().flatten().next() {
+            return Err(syn::Error::new(
+                span,
+                format!("`{option}` requires every variant to be enumerable"),
+            ));
+        }
+        return Ok(quote! { #kind });
+    }
+
+    let nested = variants
+        .iter()
+        .any(|variant| matches!(variant.shape, Shape::Fields(_)));
+    if nested && !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "Enumly cannot flatten variants of generic enums",
+        ));
+    }
+    // `Enumly` requires `Self: 'static`, which the fields of a skipped variant may not satisfy.
+    if variants
+        .iter()
+        .any(|variant| matches!(variant.shape, Shape::Skipped))
+        && !input.generics.params.is_empty()
+    {
+        return Err(syn::Error::new_spanned(
+            &input.generics.params,
+            "Enumly cannot skip variants of generic enums",
+        ));
+    }
+
+    items.extend([
+        (container.count_ident(), Some("count")),
+        (container.variants_ident(), Some("variants")),
+        (container.names_ident(), Some("names")),
+        (container.docs_ident(), Some("docs")),
+        (item("index"), None),
+        (item("from_index"), None),
+        (item("as_str"), None),
+        (item("doc"), None),
+        (item("iter"), None),
+    ]);
+    items.extend(ordinal::METHODS.iter().map(|method| (item(method), None)));
+    if !nested {
+        items.extend([
+            (item("DISCRIMINANTS"), None),
+            (item("to_discriminant"), None),
+            (item("from_discriminant"), None),
+        ]);
+    }
+    check_item_names(&container, &items)?;
+    check_item_collisions(&variants, &items)?;
+
+    let conversions = match (&container.repr_conversions, &repr) {
+        (Some(path), _) if nested => {
+            return Err(syn::Error::new(
+                path.span(),
+                "`repr_conversions` requires every variant to be a unit variant",
+            ));
+        }
+        (Some(_), Some(repr)) => Some(discriminant::expand_conversions(input, repr)),
+        (Some(path), None) => {
+            return Err(syn::Error::new(
+                path.span(),
+                "`repr_conversions` requires an integer `#[repr(...)]` on the enum",
+            ));
+        }
+        (None, _) => None,
+    };
+
+    let name = &input.ident;
+    let names_ident = container.names_ident();
+    let names = per_value(
+        &container,
+        &variants,
+        nested,
+        |variant| &variant.name,
+        "as_str",
+    );
+    let as_str_arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+        quote! { Self::#ident { .. } => #name }
+    });
+    let docs_ident = container.docs_ident();
+    let docs = per_value(&container, &variants, nested, |variant| &variant.doc, "doc");
+    let doc_arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let doc = &variant.doc;
+        quote! { Self::#ident { .. } => #doc }
+    });
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let cases: Vec<Case> = variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            Case {
+                path: quote! { Self::#ident },
+                label: format!("{}::{}", name.unraw(), ident.unraw()),
+                shape: &variant.shape,
+            }
+        })
+        .collect();
+    let index_items = index::expand(
+        &Items {
+            vis: &vis,
+            krate: &krate,
+            count: container.count_ident(),
+            variants: container.variants_ident(),
+        },
+        &cases,
+    );
+    let discriminant_items =
+        (!nested).then(|| discriminant::expand(&vis, repr.as_ref(), &variants));
+    let from_str_impl = container
+        .from_str
+        .is_some()
+        .then(|| from_str::expand(input, &container, &variants));
+    let display_impl = match &container.display {
+        Some((_, template)) => Some(display::expand(
+            input,
+            template.as_ref(),
+            &variants,
+            !nested,
+        )?),
+        None if variants.iter().any(|variant| variant.display.is_some()) => {
+            Some(display::expand(input, None, &variants, !nested)?)
+        }
+        None => None,
+    };
+    let serde_impl = match &container.serde {
+        Some(serde) => {
+            let repr = (!nested).then(|| match &repr {
+                Some(repr) => quote! { #repr },
+                None => quote! { isize },
+            });
+            Some(serde::expand(
+                input,
+                &container,
+                serde,
+                &variants,
+                repr.as_ref(),
+            )?)
+        }
+        None => None,
+    };
+    let clap_impl = match &container.clap {
+        Some(clap) => Some(clap::expand(input, &container, clap, &variants)?),
+        None => None,
+    };
+    let map = match &container.map {
+        Some(map) => Some(map::expand(input, &vis, &krate, map)?),
+        None => None,
+    };
+    let set = match &container.set {
+        Some(set) => Some(set::expand(
+            input,
+            &vis,
+            &krate,
+            set,
+            (!nested).then(|| {
+                variants
+                    .iter()
+                    .filter(|variant| matches!(variant.shape, Shape::Unit))
+                    .count()
+            }),
+            // Flattened values share their variant's name, so they are told apart by index.
+            !nested,
+        )?),
+        None => None,
+    };
+    let trait_impl = trait_impl(input, &container, &krate);
+    let iter = iter::expand(input, &vis, &krate, container.iter.as_ref());
+    let ordinal_items = ordinal::expand(&vis, &krate);
+
+    Ok(quote! {
+        #trait_impl
 
-    let expanded = quote! {
         impl #impl_generics #name #ty_generics #where_clause {
-            pub const COUNT: usize = #count;
-            pub const VARIANTS: &'static [Self] = &[#(#variant_exprs),*];
+            #index_items
+            #ordinal_items
+            #vis const #names_ident: &'static [&'static str] = #names;
+
+            #vis const fn as_str(&self) -> &'static str {
+                match *self {
+                    #(#as_str_arms,)*
+                }
+            }
+
+            #vis const #docs_ident: &'static [&'static str] = #docs;
+
+            #vis const fn doc(&self) -> &'static str {
+                match *self {
+                    #(#doc_arms,)*
+                }
+            }
+
+            #discriminant_items
         }
+
+        #iter
+        #display_impl
+        #from_str_impl
+        #serde_impl
+        #clap_impl
+        #conversions
+        #map
+        #set
+        #kind
+    })
+}
+
+/// Structs are enumerated as the cartesian product of their fields; they have no names or
+/// discriminants, so only the positional items, `map` and `set` are generated.
+fn expand_struct(
+    input: &DeriveInput,
+    data_struct: &DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let container = ContainerAttrs::parse(&input.attrs, Target::Struct)?;
+    let vis = container.vis.clone().unwrap_or_else(|| input.vis.clone());
+    let krate = container.krate();
+    let shape = match &data_struct.fields {
+        Fields::Unit => Shape::Unit,
+        fields => Shape::Fields(Product::classify(fields, unsupported_field)?),
     };
+    if matches!(shape, Shape::Fields(_)) && !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "Enumly cannot enumerate the fields of generic structs",
+        ));
+    }
+
+    let mut items = vec![
+        (container.count_ident(), Some("count")),
+        (container.variants_ident(), Some("variants")),
+        (item("index"), None),
+        (item("from_index"), None),
+        (item("iter"), None),
+    ];
+    items.extend(ordinal::METHODS.iter().map(|method| (item(method), None)));
+    check_item_names(&container, &items)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let index_items = index::expand(
+        &Items {
+            vis: &vis,
+            krate: &krate,
+            count: container.count_ident(),
+            variants: container.variants_ident(),
+        },
+        &[Case {
+            path: quote! { Self },
+            label: name.unraw().to_string(),
+            shape: &shape,
+        }],
+    );
+    let map = match &container.map {
+        Some(map) => Some(map::expand(input, &vis, &krate, map)?),
+        None => None,
+    };
+    let set = match &container.set {
+        Some(set) => Some(set::expand(input, &vis, &krate, set, None, false)?),
+        None => None,
+    };
+    let trait_impl = trait_impl(input, &container, &krate);
+    let iter = iter::expand(input, &vis, &krate, container.iter.as_ref());
+    let ordinal_items = ordinal::expand(&vis, &krate);
+
+    Ok(quote! {
+        #trait_impl
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #index_items
+            #ordinal_items
+        }
+
+        #iter
+        #map
+        #set
+    })
+}
+
+/// Implements `enumly::Enumly` by forwarding to the inherent items.
+fn trait_impl(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    krate: &Path,
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let count = container.count_ident();
+    let variants = container.variants_ident();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::Enumly for #name #ty_generics #where_clause {
+            const COUNT: usize = Self::#count;
+            const VARIANTS: &'static [Self] = Self::#variants;
+
+            fn index(&self) -> usize {
+                Self::index(self)
+            }
+
+            fn from_index(index: usize) -> ::core::option::Option<Self> {
+                Self::from_index(index)
+            }
+        }
+    }
+}
+
+/// A variant together with the strings it is exposed and parsed under.
+struct Variant {
+    ident: Ident,
+    name: String,
+    name_span: Span,
+    aliases: Vec<LitStr>,
+    discriminant: Option<Expr>,
+    attrs: Vec<Attribute>,
+    hidden: bool,
+    display: Option<LitStr>,
+    doc: String,
+    shape: Shape,
+}
+
+impl Variant {
+    /// Whether the variant is accepted by `FromStr` and offered as a command-line value.
+    fn is_named(&self) -> bool {
+        !self.hidden && !matches!(self.shape, Shape::Skipped)
+    }
+}
+
+/// How the values of a variant or struct are enumerated.
+enum Shape {
+    /// No fields, listed once.
+    Unit,
+    /// Listed once for every combination of field values.
+    Fields(Product),
+    /// A variant whose fields are not enumerated; only allowed together with `kind`.
+    Opaque,
+    /// A variant marked `skip`, left out of every list; its fields are never inspected.
+    Skipped,
+}
+
+/// Initializer of a constant holding one string per value, index-aligned with `VARIANTS`.
+/// Without flattened variants the strings are listed directly; otherwise each value is read
+/// back through `method` while evaluating the constant.
+fn per_value(
+    container: &ContainerAttrs,
+    variants: &[Variant],
+    nested: bool,
+    text: impl Fn(&Variant) -> &String,
+    method: &str,
+) -> proc_macro2::TokenStream {
+    if !nested {
+        let texts = variants
+            .iter()
+            .filter(|variant| !matches!(variant.shape, Shape::Skipped))
+            .map(text);
+        return quote! { &[#(#texts),*] };
+    }
+
+    let count = container.count_ident();
+    let variants = container.variants_ident();
+    let method = item(method);
+    quote! {
+        &{
+            let mut texts = [""; Self::#count];
+            let mut index = 0;
+            while index < Self::#count {
+                texts[index] = Self::#variants[index].#method();
+                index += 1;
+            }
+            texts
+        }
+    }
+}
+
+fn unsupported_field(field: &Field) -> syn::Error {
+    syn::Error::new(
+        field.ty.span(),
+        "Enumly can only enumerate fields holding an Enumly type, `bool`, `Option<_>` or an \
+         integer with `#[enumly(range = ...)]`",
+    )
+}
+
+/// Visibility of a generated error type. It is the error of a trait impl on the type, so it
+/// cannot be less visible than the type itself and keeps the type's own visibility whatever
+/// `vis` says.
+pub(crate) fn trait_error_vis(input: &DeriveInput) -> &Visibility {
+    &input.vis
+}
+
+fn item(name: &str) -> Ident {
+    Ident::new(name, Span::call_site())
+}
+
+/// Rejects variants named like a generated associated item, since `Self::NAME` would resolve
+/// to the variant instead of the item.
+fn check_item_collisions(variants: &[Variant], items: &[(Ident, Option<&str>)]) -> syn::Result<()> {
+    for variant in variants {
+        if let Some((item, option)) = items.iter().find(|(item, _)| variant.ident == *item) {
+            let hint = match option {
+                Some(option) => {
+                    format!("; choose another name with `#[enumly({option} = \"...\")]`")
+                }
+                None => String::new(),
+            };
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!(
+                    "variant `{}` collides with the generated associated item `{item}`{hint}",
+                    variant.ident
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects replacement item names, such as `count = "NAMES"`, that clash with another generated
+/// associated item.
+fn check_item_names(container: &ContainerAttrs, items: &[(Ident, Option<&str>)]) -> syn::Result<()> {
+    let configured = [
+        ("count", &container.count),
+        ("variants", &container.variants),
+        ("names", &container.names),
+        ("docs", &container.docs),
+    ];
+    for (option, name) in configured {
+        if let Some(name) = name
+            && items.iter().filter(|(item, _)| item == name).count() > 1
+        {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("`{option}` name `{name}` collides with another generated associated item"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects names and aliases that would make parsing ambiguous.
+fn check_collisions(variants: &[Variant], ascii_case_insensitive: bool) -> syn::Result<()> {
+    let mut seen: Vec<(String, &Ident)> = Vec::new();
+
+    for variant in variants.iter().filter(|variant| variant.is_named()) {
+        let accepted = std::iter::once((variant.name.clone(), variant.name_span)).chain(
+            variant
+                .aliases
+                .iter()
+                .map(|alias| (alias.value(), alias.span())),
+        );
+
+        for (value, span) in accepted {
+            let key = match ascii_case_insensitive {
+                true => value.to_ascii_lowercase(),
+                false => value.clone(),
+            };
+            if let Some((_, owner)) = seen.iter().find(|(seen_key, _)| *seen_key == key) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{value}` is already accepted for variant `{owner}`"),
+                ));
+            }
+            seen.push((key, &variant.ident));
+        }
+    }
 
-    TokenStream::from(expanded)
+    Ok(())
 }
 
 fn non_exhaustive_error(attrs: &[Attribute]) -> Option<syn::Error> {
@@ -0,0 +1,132 @@
+//! Fixed-size map type keyed by the enum, generated for `#[enumly(map = Name)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Ident, Path, Visibility};
+
+pub(crate) fn expand(
+    input: &DeriveInput,
+    vis: &Visibility,
+    krate: &Path,
+    map: &Ident,
+) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            map.span(),
+            "`map` is not supported for generic types",
+        ));
+    }
+
+    let name = &input.ident;
+    let count = quote! { <#name as #krate::Enumly>::COUNT };
+    let map_doc = format!(
+        "A map holding one value for every [`{}`] variant, stored inline in declaration order.",
+        name.unraw()
+    );
+
+    Ok(quote! {
+        #[doc = #map_doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #vis struct #map<V> {
+            values: [V; #count],
+        }
+
+        impl<V> #map<V> {
+            /// Creates a map by calling `f` with every key in declaration order.
+            #vis fn from_fn(mut f: impl FnMut(#name) -> V) -> Self {
+                Self {
+                    values: ::core::array::from_fn(|index| f(Self::key(index))),
+                }
+            }
+
+            /// Creates a map from values given in declaration order.
+            #vis const fn from_array(values: [V; #count]) -> Self {
+                Self { values }
+            }
+
+            /// Returns the values in declaration order.
+            #vis fn into_array(self) -> [V; #count] {
+                self.values
+            }
+
+            /// Returns the value stored for `key`.
+            #vis const fn get(&self, key: &#name) -> &V {
+                &self.values[key.index()]
+            }
+
+            /// Returns a mutable reference to the value stored for `key`.
+            #vis const fn get_mut(&mut self, key: &#name) -> &mut V {
+                &mut self.values[key.index()]
+            }
+
+            /// Iterates over every key together with a reference to its value.
+            #vis fn iter(
+                &self,
+            ) -> impl ::core::iter::DoubleEndedIterator<Item = (#name, &V)>
+                   + ::core::iter::ExactSizeIterator
+            {
+                self.values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| (Self::key(index), value))
+            }
+
+            /// Iterates over every key together with a mutable reference to its value.
+            #vis fn iter_mut(
+                &mut self,
+            ) -> impl ::core::iter::DoubleEndedIterator<Item = (#name, &mut V)>
+                   + ::core::iter::ExactSizeIterator
+            {
+                self.values
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(index, value)| (Self::key(index), value))
+            }
+
+            /// Returns the values in declaration order.
+            #vis const fn values(&self) -> &[V] {
+                &self.values
+            }
+
+            /// Creates a new map by applying `f` to every key and value.
+            #vis fn map<U>(self, mut f: impl FnMut(#name, V) -> U) -> #map<U> {
+                let mut index = 0;
+                #map {
+                    values: self.values.map(|value| {
+                        let key = Self::key(index);
+                        index += 1;
+                        f(key, value)
+                    }),
+                }
+            }
+
+            fn key(index: usize) -> #name {
+                match #name::from_index(index) {
+                    ::core::option::Option::Some(key) => key,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+        }
+
+        impl<V: ::core::default::Default> ::core::default::Default for #map<V> {
+            fn default() -> Self {
+                Self::from_fn(|_| ::core::default::Default::default())
+            }
+        }
+
+        impl<V> ::core::ops::Index<#name> for #map<V> {
+            type Output = V;
+
+            fn index(&self, key: #name) -> &V {
+                self.get(&key)
+            }
+        }
+
+        impl<V> ::core::ops::IndexMut<#name> for #map<V> {
+            fn index_mut(&mut self, key: #name) -> &mut V {
+                self.get_mut(&key)
+            }
+        }
+    })
+}
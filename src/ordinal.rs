@@ -0,0 +1,76 @@
+//! Navigation between neighbouring values in index order.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Path, Visibility};
+
+/// Names of the generated methods, for collision checks.
+pub(crate) const METHODS: &[&str] = &[
+    "next",
+    "prev",
+    "next_wrapping",
+    "prev_wrapping",
+    "checked_offset",
+    "distance",
+    "is_first",
+    "is_last",
+];
+
+pub(crate) fn expand(vis: &Visibility, krate: &Path) -> TokenStream {
+    let count = quote! { <Self as #krate::Enumly>::COUNT };
+
+    quote! {
+        /// Returns the value after `self`, or `None` if `self` is the last one.
+        #vis const fn next(&self) -> ::core::option::Option<Self> {
+            Self::from_index(self.index() + 1)
+        }
+
+        /// Returns the value before `self`, or `None` if `self` is the first one.
+        #vis const fn prev(&self) -> ::core::option::Option<Self> {
+            match self.index() {
+                0 => ::core::option::Option::None,
+                index => Self::from_index(index - 1),
+            }
+        }
+
+        /// Returns the value after `self`, wrapping around to the first one.
+        #vis const fn next_wrapping(&self) -> Self {
+            match self.index() + 1 {
+                index if index == #count => ::core::option::Option::unwrap(Self::from_index(0)),
+                index => ::core::option::Option::unwrap(Self::from_index(index)),
+            }
+        }
+
+        /// Returns the value before `self`, wrapping around to the last one.
+        #vis const fn prev_wrapping(&self) -> Self {
+            match self.index() {
+                0 => ::core::option::Option::unwrap(Self::from_index(#count - 1)),
+                index => ::core::option::Option::unwrap(Self::from_index(index - 1)),
+            }
+        }
+
+        /// Returns the value `offset` positions away from `self`, or `None` if that falls
+        /// outside the list.
+        #vis const fn checked_offset(&self, offset: isize) -> ::core::option::Option<Self> {
+            match self.index().checked_add_signed(offset) {
+                ::core::option::Option::Some(index) => Self::from_index(index),
+                ::core::option::Option::None => ::core::option::Option::None,
+            }
+        }
+
+        /// Returns how many positions `other` is after `self`; negative if it comes before.
+        #vis const fn distance(&self, other: &Self) -> isize {
+            other.index() as isize - self.index() as isize
+        }
+
+        /// Returns `true` if `self` is the first value.
+        #vis const fn is_first(&self) -> bool {
+            self.index() == 0
+        }
+
+        /// Returns `true` if `self` is the last value.
+        #vis const fn is_last(&self) -> bool {
+            self.index() + 1 == #count
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! `Serialize`/`Deserialize` implementations for `#[enumly(serde = "...")]`, available with
+//! the `serde` feature.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Ident, LitStr};
+
+use crate::attr::ContainerAttrs;
+use crate::{Shape, Variant};
+
+/// How a value is written to and read from the serde data model.
+enum Repr {
+    /// The string name, parsed back with `FromStr` so aliases are accepted.
+    Name,
+    /// The position in `VARIANTS`, as a `u64`.
+    Index,
+    /// The value of `to_discriminant`.
+    Discriminant,
+}
+
+/// `repr` is the integer type of the discriminants, or `None` when they are not generated
+/// because some variants are flattened.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    container: &ContainerAttrs,
+    serde: &LitStr,
+    variants: &[Variant],
+    repr: Option<&TokenStream>,
+) -> syn::Result<TokenStream> {
+    let mode = match serde.value().as_str() {
+        "name" => Repr::Name,
+        "index" => Repr::Index,
+        "discriminant" => Repr::Discriminant,
+        other => {
+            return Err(syn::Error::new(
+                serde.span(),
+                format!(
+                    "unknown serde representation `{other}`; expected \"name\", \"index\" or \
+                     \"discriminant\""
+                ),
+            ));
+        }
+    };
+
+    let nested = variants
+        .iter()
+        .any(|variant| matches!(variant.shape, Shape::Fields(_)));
+    if nested && matches!(mode, Repr::Name) {
+        return Err(syn::Error::new(
+            serde.span(),
+            "`serde = \"name\"` requires every variant to be a unit variant",
+        ));
+    }
+
+    if matches!(mode, Repr::Name) && container.from_str.is_none() {
+        return Err(syn::Error::new(
+            serde.span(),
+            "`serde = \"name\"` reads names back through `FromStr`; add `from_str`",
+        ));
+    }
+
+    // Names are read back through `FromStr`, which does not accept hidden variants.
+    if let Some(hidden) = variants
+        .iter()
+        .find(|variant| variant.hidden && matches!(mode, Repr::Name))
+    {
+        return Err(syn::Error::new(
+            serde.span(),
+            format!(
+                "`serde = \"name\"` cannot read back hidden variant `{}`; use \"index\" or \
+                 \"discriminant\", or remove `hidden`",
+                hidden.ident.unraw()
+            ),
+        ));
+    }
+
+    let name = &input.ident;
+    let enum_name = name.unraw().to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, syn::parse_quote! { 'de });
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    // Skipped variants cannot be read back, so writing them is an error.
+    let skipped_arms = variants
+        .iter()
+        .filter(|variant| matches!(variant.shape, Shape::Skipped))
+        .map(|variant| {
+            let ident = &variant.ident;
+            let message = format!(
+                "`{enum_name}::{}` is skipped by Enumly and cannot be serialized",
+                ident.unraw()
+            );
+            quote! {
+                Self::#ident { .. } => {
+                    return ::core::result::Result::Err(::serde::ser::Error::custom(#message));
+                }
+            }
+        });
+    let check_skipped = quote! {
+        match *self {
+            #(#skipped_arms)*
+            _ => {}
+        }
+    };
+
+    let (serialize, deserialize) = match mode {
+        Repr::Name => (
+            quote! { serializer.serialize_str(self.as_str()) },
+            deserialize_name(name),
+        ),
+        Repr::Index => (
+            quote! { serializer.serialize_u64(self.index() as u64) },
+            quote! {
+                let index = <u64 as ::serde::Deserialize>::deserialize(deserializer)?;
+                match <usize as ::core::convert::TryFrom<u64>>::try_from(index)
+                    .ok()
+                    .and_then(Self::from_index)
+                {
+                    ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                    ::core::option::Option::None => ::core::result::Result::Err(
+                        ::serde::de::Error::custom(::core::format_args!(
+                            "invalid {} index `{}`",
+                            #enum_name,
+                            index
+                        )),
+                    ),
+                }
+            },
+        ),
+        Repr::Discriminant => {
+            let Some(repr) = repr else {
+                return Err(syn::Error::new(
+                    serde.span(),
+                    "`serde = \"discriminant\"` requires every variant to be a unit variant",
+                ));
+            };
+            (
+                quote! { ::serde::Serialize::serialize(&self.to_discriminant(), serializer) },
+                quote! {
+                    let value = <#repr as ::serde::Deserialize>::deserialize(deserializer)?;
+                    match Self::from_discriminant(value) {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                        ::core::option::Option::None => ::core::result::Result::Err(
+                            ::serde::de::Error::custom(::core::format_args!(
+                                "invalid {} discriminant `{}`",
+                                #enum_name,
+                                value
+                            )),
+                        ),
+                    }
+                },
+            )
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<S: ::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                #check_skipped
+                #serialize
+            }
+        }
+
+        impl #de_impl_generics ::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                #deserialize
+            }
+        }
+    })
+}
+
+/// Reads a string through `FromStr`, reporting unknown names with the accepted ones.
+fn deserialize_name(name: &Ident) -> TokenStream {
+    let error = format_ident!("Parse{}Error", name.unraw(), span = name.span());
+    let expecting = format!("a {} name", name.unraw());
+
+    quote! {
+        struct Visitor<T>(::core::marker::PhantomData<fn() -> T>);
+
+        impl<'de, T> ::serde::de::Visitor<'de> for Visitor<T>
+        where
+            T: ::core::str::FromStr<Err = #error>,
+        {
+            type Value = T;
+
+            fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(#expecting)
+            }
+
+            fn visit_str<E: ::serde::de::Error>(
+                self,
+                value: &str,
+            ) -> ::core::result::Result<T, E> {
+                value
+                    .parse()
+                    .map_err(|err: #error| E::unknown_variant(value, err.expected()))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor(::core::marker::PhantomData))
+    }
+}
@@ -0,0 +1,250 @@
+//! Compact bitset type over the enum, generated for `#[enumly(set = Name)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Ident, Path, Visibility};
+
+/// `count` is the number of variants when it is known while expanding; otherwise the set
+/// is sized from `COUNT` using `u64` words. `named` types format their members with
+/// `as_str`, others with their indices.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    vis: &Visibility,
+    krate: &Path,
+    set: &Ident,
+    count: Option<usize>,
+    named: bool,
+) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            set.span(),
+            "`set` is not supported for generic types",
+        ));
+    }
+
+    let name = &input.ident;
+    let word = word_type(count);
+    let count = quote! { <#name as #krate::Enumly>::COUNT };
+    let words = quote! { #count.div_ceil(#word::BITS as usize) };
+    let set_doc = format!(
+        "A set of [`{}`] variants stored as a bitset, one bit per variant.",
+        name.unraw()
+    );
+
+    let entry = match named {
+        true => quote! { variant.as_str() },
+        false => quote! { variant.index() },
+    };
+
+    Ok(quote! {
+        #[doc = #set_doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #set {
+            bits: [#word; #words],
+        }
+
+        impl #set {
+            const WORD_BITS: usize = #word::BITS as usize;
+
+            /// Returns a set containing no variants.
+            #vis const fn empty() -> Self {
+                Self { bits: [0; #words] }
+            }
+
+            /// Returns a set containing every variant.
+            #vis const fn all() -> Self {
+                let mut set = Self::empty();
+                let mut index = 0;
+                while index < #count {
+                    set.bits[index / Self::WORD_BITS] |= 1 << (index % Self::WORD_BITS);
+                    index += 1;
+                }
+                set
+            }
+
+            /// Returns a set containing the given variants.
+            #vis const fn from_slice(variants: &[#name]) -> Self {
+                let mut set = Self::empty();
+                let mut i = 0;
+                while i < variants.len() {
+                    let index = variants[i].index();
+                    set.bits[index / Self::WORD_BITS] |= 1 << (index % Self::WORD_BITS);
+                    i += 1;
+                }
+                set
+            }
+
+            /// Returns the number of variants in the set.
+            #vis const fn len(&self) -> usize {
+                let mut len = 0;
+                let mut i = 0;
+                while i < self.bits.len() {
+                    len += self.bits[i].count_ones() as usize;
+                    i += 1;
+                }
+                len
+            }
+
+            /// Returns `true` if the set contains no variants.
+            #vis const fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns `true` if the set contains `variant`.
+            #vis const fn contains(&self, variant: &#name) -> bool {
+                self.contains_index(variant.index())
+            }
+
+            /// Adds `variant`, returning `true` if it was not already present.
+            #vis fn insert(&mut self, variant: #name) -> bool {
+                let index = variant.index();
+                let added = !self.contains_index(index);
+                self.bits[index / Self::WORD_BITS] |= 1 << (index % Self::WORD_BITS);
+                added
+            }
+
+            /// Removes `variant`, returning `true` if it was present.
+            #vis fn remove(&mut self, variant: #name) -> bool {
+                let index = variant.index();
+                let removed = self.contains_index(index);
+                self.bits[index / Self::WORD_BITS] &= !(1 << (index % Self::WORD_BITS));
+                removed
+            }
+
+            /// Returns the variants present in either set.
+            #vis const fn union(mut self, other: Self) -> Self {
+                let mut i = 0;
+                while i < self.bits.len() {
+                    self.bits[i] |= other.bits[i];
+                    i += 1;
+                }
+                self
+            }
+
+            /// Returns the variants present in both sets.
+            #vis const fn intersection(mut self, other: Self) -> Self {
+                let mut i = 0;
+                while i < self.bits.len() {
+                    self.bits[i] &= other.bits[i];
+                    i += 1;
+                }
+                self
+            }
+
+            /// Returns the variants present in `self` but not in `other`.
+            #vis const fn difference(mut self, other: Self) -> Self {
+                let mut i = 0;
+                while i < self.bits.len() {
+                    self.bits[i] &= !other.bits[i];
+                    i += 1;
+                }
+                self
+            }
+
+            /// Returns the variants not present in `self`.
+            #vis const fn complement(self) -> Self {
+                Self::all().difference(self)
+            }
+
+            /// Returns `true` if every variant in `self` is also in `other`.
+            #vis const fn is_subset(&self, other: &Self) -> bool {
+                let mut i = 0;
+                while i < self.bits.len() {
+                    if self.bits[i] & !other.bits[i] != 0 {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+
+            /// Iterates over the variants in the set in declaration order.
+            #vis fn iter(&self) -> impl ::core::iter::DoubleEndedIterator<Item = #name> {
+                let set = *self;
+                (0..#count)
+                    .filter(move |&index| set.contains_index(index))
+                    .filter_map(#name::from_index)
+            }
+
+            const fn contains_index(&self, index: usize) -> bool {
+                self.bits[index / Self::WORD_BITS] & (1 << (index % Self::WORD_BITS)) != 0
+            }
+        }
+
+        impl ::core::default::Default for #set {
+            fn default() -> Self {
+                Self::empty()
+            }
+        }
+
+        impl ::core::fmt::Debug for #set {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_set()
+                    .entries(self.iter().map(|variant| #entry))
+                    .finish()
+            }
+        }
+
+        impl ::core::iter::FromIterator<#name> for #set {
+            fn from_iter<I: ::core::iter::IntoIterator<Item = #name>>(iter: I) -> Self {
+                let mut set = Self::empty();
+                ::core::iter::Extend::extend(&mut set, iter);
+                set
+            }
+        }
+
+        impl ::core::iter::Extend<#name> for #set {
+            fn extend<I: ::core::iter::IntoIterator<Item = #name>>(&mut self, iter: I) {
+                for variant in iter {
+                    self.insert(variant);
+                }
+            }
+        }
+
+        impl ::core::ops::BitOr for #set {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                self.union(other)
+            }
+        }
+
+        impl ::core::ops::BitAnd for #set {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                self.intersection(other)
+            }
+        }
+
+        impl ::core::ops::Sub for #set {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                self.difference(other)
+            }
+        }
+
+        impl ::core::ops::Not for #set {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                self.complement()
+            }
+        }
+    })
+}
+
+/// Picks the smallest unsigned integer that holds one bit per variant, falling back to
+/// an array of `u64` words for enums with more than 128 variants or an unknown count.
+fn word_type(count: Option<usize>) -> TokenStream {
+    match count {
+        Some(0..=8) => quote! { u8 },
+        Some(9..=16) => quote! { u16 },
+        Some(17..=32) => quote! { u32 },
+        Some(33..=64) => quote! { u64 },
+        Some(65..=128) => quote! { u128 },
+        _ => quote! { u64 },
+    }
+}
@@ -0,0 +1,67 @@
+//! Clap accepts exactly the values `FromStr` accepts.
+
+use clap::{Parser, ValueEnum};
+use enumly::Enumly;
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(clap, from_str)]
+enum Level {
+    /// Quiet output.
+    ///
+    /// Only errors are printed.
+    Low,
+    #[enumly(alias = "hi")]
+    High,
+    #[enumly(hidden)]
+    Legacy,
+    #[enumly(skip)]
+    Internal,
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(clap, from_str, rename_all = "lowercase")]
+enum Color {
+    Red,
+    #[enumly(alias = "g")]
+    Green,
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long, value_enum)]
+    level: Option<Level>,
+    #[arg(long, value_enum)]
+    color: Option<Color>,
+}
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_from(std::iter::once("app").chain(args.iter().copied()))
+}
+
+#[test]
+fn possible_values_follow_the_variants() {
+    assert_eq!(Level::value_variants(), &[Level::Low, Level::High]);
+    let low = Level::Low.to_possible_value().unwrap();
+    assert_eq!(low.get_name(), "Low");
+    assert_eq!(low.get_help().unwrap().to_string(), "Quiet output.");
+    assert!(Level::Legacy.to_possible_value().is_none());
+    assert!(Level::Internal.to_possible_value().is_none());
+}
+
+#[test]
+fn clap_and_from_str_agree() {
+    for input in ["Low", "High", "hi", "Legacy", "Internal", "low"] {
+        let parsed = parse(&["--level", input]).ok().and_then(|cli| cli.level);
+        assert_eq!(parsed, input.parse::<Level>().ok(), "{input}");
+    }
+    for input in ["red", "RED", "Green", "G", "blue"] {
+        let parsed = parse(&["--color", input]).ok().and_then(|cli| cli.color);
+        assert_eq!(parsed, input.parse::<Color>().ok(), "{input}");
+    }
+}
+
+#[test]
+fn rejected_values_list_the_accepted_ones() {
+    let err = parse(&["--color", "blue"]).err().unwrap().to_string();
+    assert!(err.contains("[possible values: red, green]"), "{err}");
+}
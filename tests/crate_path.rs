@@ -0,0 +1,59 @@
+//! `crate = "..."` points the expansion at a facade reached through another path.
+
+mod facade {
+    pub use enumly_derive::Enumly;
+
+    /// A local copy of the facade trait, so only impls that follow `crate` satisfy it.
+    pub trait Enumly: Sized + 'static {
+        const COUNT: usize;
+        const VARIANTS: &'static [Self];
+
+        fn index(&self) -> usize;
+
+        fn from_index(index: usize) -> Option<Self>;
+    }
+}
+
+use facade::Enumly;
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(crate = "crate::facade")]
+enum Inner {
+    A,
+    B,
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(crate = "crate::facade", map = OuterMap, set = OuterSet)]
+enum Outer {
+    Inner(Inner),
+    Last,
+}
+
+#[derive(Enumly)]
+#[enumly(crate = "crate::facade", kind = MessageKind)]
+#[allow(dead_code)]
+enum Message {
+    Text(String),
+    Quit,
+}
+
+/// Checks the trait impl through the local trait and returns its count.
+fn count<E: facade::Enumly + PartialEq + std::fmt::Debug>() -> usize {
+    for (index, value) in E::VARIANTS.iter().enumerate() {
+        assert_eq!(facade::Enumly::index(value), index);
+        assert_eq!(E::from_index(index).as_ref(), Some(value));
+    }
+    E::COUNT
+}
+
+#[test]
+fn expansion_follows_the_crate_path() {
+    assert_eq!(count::<Outer>(), 3);
+    assert_eq!(count::<MessageKind>(), 2);
+    assert_eq!(Outer::Last.prev(), Some(Outer::Inner(Inner::B)));
+    assert_eq!(Outer::iter().len(), 3);
+    assert_eq!(OuterMap::from_fn(|outer| outer.index())[Outer::Last], 2);
+    assert_eq!(OuterSet::all().len(), 3);
+    assert_eq!(Message::Text(String::new()).kind(), MessageKind::Text);
+}
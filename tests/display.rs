@@ -0,0 +1,33 @@
+//! Bare names honour the formatter's padding, and a variant template wins over the enum's.
+
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(display, rename_all = "lowercase")]
+enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Enumly)]
+#[enumly(display = "{index}. {name}")]
+enum Step {
+    Fetch,
+    #[enumly(display = "{name}!")]
+    Build,
+    Ship,
+}
+
+#[test]
+fn bare_display_pads_the_name() {
+    assert_eq!(format!("{:>6}", Side::Left), "  left");
+    assert_eq!(format!("{:-<7}|", Side::Right), "right--|");
+    assert_eq!(format!("{:.2}", Side::Right), "ri");
+}
+
+#[test]
+fn variant_template_overrides_the_enum_template() {
+    assert_eq!(Step::Fetch.to_string(), "0. Fetch");
+    assert_eq!(Step::Build.to_string(), "Build!");
+    assert_eq!(Step::Ship.to_string(), "2. Ship");
+}
@@ -0,0 +1,38 @@
+//! Flattened values repeat their variant's name, keeping `NAMES` index-aligned with `VARIANTS`.
+
+use enumly::Enumly;
+
+#[derive(Enumly, Debug, PartialEq)]
+enum Digit {
+    Zero,
+    One,
+}
+
+#[derive(Enumly, Debug, PartialEq)]
+#[enumly(set = KeySet)]
+enum Key {
+    /// A digit key.
+    Digit(Digit),
+    #[enumly(hidden)]
+    Shift,
+    /// The space bar.
+    Space,
+}
+
+#[test]
+fn names_and_docs_are_index_aligned() {
+    assert_eq!(Key::NAMES, &["Digit", "Digit", "Shift", "Space"]);
+    for key in Key::VARIANTS {
+        assert_eq!(Key::NAMES[key.index()], key.as_str());
+        assert_eq!(Key::DOCS[key.index()], key.doc());
+    }
+}
+
+#[test]
+fn set_formats_flattened_members_by_index() {
+    let mut set = KeySet::empty();
+    set.insert(Key::Digit(Digit::Zero));
+    set.insert(Key::Digit(Digit::One));
+    set.insert(Key::Space);
+    assert_eq!(format!("{set:?}"), "{0, 1, 3}");
+}
@@ -0,0 +1,40 @@
+//! `FromStr` is only implemented on request, so a hand-written impl keeps compiling.
+
+use std::str::FromStr;
+
+use enumly::Enumly;
+
+#[derive(Enumly, Debug, PartialEq)]
+enum Handwritten {
+    Up,
+    Down,
+}
+
+impl FromStr for Handwritten {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "↑" => Ok(Self::Up),
+            "↓" => Ok(Self::Down),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Enumly, Debug, PartialEq)]
+#[enumly(from_str, rename_all = "lowercase")]
+enum Generated {
+    Up,
+    Down,
+}
+
+#[test]
+fn from_str_is_opt_in() {
+    assert_eq!("↓".parse::<Handwritten>(), Ok(Handwritten::Down));
+    assert_eq!("up".parse::<Generated>(), Ok(Generated::Up));
+
+    let err = "Up".parse::<Generated>().unwrap_err();
+    assert_eq!(err.input(), "Up");
+    assert_eq!(err.to_string(), "unknown Generated `Up`; expected one of `up`, `down`");
+}
@@ -0,0 +1,31 @@
+//! Lookups by key, including in `const` contexts.
+
+use enumly::Enumly;
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(map = LevelMap)]
+enum Level {
+    Low,
+    High,
+}
+
+const LIMITS: LevelMap<u32> = LevelMap::from_array([10, 20]);
+const HIGH_LIMIT: u32 = *LIMITS.get(&Level::High);
+
+#[test]
+fn get_works_in_const_contexts() {
+    assert_eq!(HIGH_LIMIT, 20);
+}
+
+#[test]
+fn values_are_reached_by_key() {
+    let mut map = LevelMap::<u32>::default();
+    *map.get_mut(&Level::High) += 1;
+    map[Level::Low] += 2;
+    assert_eq!(*map.get(&Level::Low), 2);
+    assert_eq!(map[Level::High], 1);
+    assert_eq!(
+        map.iter().map(|(key, &value)| (key, value)).collect::<Vec<_>>(),
+        [(Level::Low, 2), (Level::High, 1)]
+    );
+}
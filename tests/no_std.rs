@@ -0,0 +1,20 @@
+//! The default expansion and `repr_conversions` only refer to `core`.
+
+#![no_std]
+
+use enumly::Enumly;
+
+#[derive(Enumly, Debug, PartialEq)]
+#[enumly(repr_conversions)]
+#[repr(u8)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[test]
+fn expands_without_std() {
+    assert_eq!(Color::COUNT, 2);
+    assert_eq!(Color::try_from(1), Ok(Color::Green));
+    assert!(Color::try_from(2).is_err());
+}
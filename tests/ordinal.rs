@@ -0,0 +1,15 @@
+//! Wrapping navigation on enums with a single value.
+
+use enumly::Enumly;
+
+#[derive(Enumly, Debug, PartialEq)]
+enum Only {
+    One,
+}
+
+#[test]
+fn a_single_value_wraps_to_itself() {
+    assert_eq!(Only::One.next_wrapping(), Only::One);
+    assert_eq!(Only::One.prev_wrapping(), Only::One);
+    assert!(Only::One.is_first() && Only::One.is_last());
+}
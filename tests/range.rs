@@ -0,0 +1,77 @@
+//! Integers outside their declared `range` have no index of their own.
+
+use enumly::Enumly;
+
+#[derive(Enumly, Debug, PartialEq)]
+enum Alias {
+    Level(#[enumly(range = 0..=2)] u8),
+    Off,
+}
+
+#[derive(Enumly, Debug, PartialEq)]
+struct Card {
+    #[enumly(range = 1..14)]
+    rank: u8,
+    face_up: bool,
+}
+
+#[derive(Enumly, Debug, PartialEq)]
+struct Pinned {
+    #[enumly(range = 0..=0)]
+    slot: u8,
+}
+
+const TOP: u16 = 256;
+
+#[derive(Enumly, Debug, PartialEq)]
+struct Edge {
+    #[enumly(range = 250..TOP)]
+    value: u8,
+}
+
+#[test]
+fn values_inside_the_range_are_indexed() {
+    assert_eq!(Alias::Level(2).index(), 2);
+    assert_eq!(Alias::Off.index(), 3);
+    assert_eq!(Card::COUNT, 26);
+    assert_eq!(
+        Card {
+            rank: 13,
+            face_up: true
+        }
+        .index(),
+        25
+    );
+    assert_eq!(Pinned { slot: 0 }.index(), 0);
+}
+
+#[test]
+fn ranges_reaching_the_end_of_the_type_round_trip() {
+    assert_eq!(Edge::COUNT, 6);
+    assert_eq!(Edge::VARIANTS.last(), Some(&Edge { value: u8::MAX }));
+    for value in Edge::VARIANTS {
+        assert_eq!(&Edge::VARIANTS[value.index()], value);
+    }
+}
+
+#[test]
+#[should_panic(expected = "`Alias::Level` holds a value outside its Enumly `range`")]
+fn values_above_the_range_panic() {
+    Alias::Level(3).index();
+}
+
+#[test]
+#[should_panic(expected = "`Card` holds a value outside its Enumly `range`")]
+fn values_below_the_range_panic() {
+    Card {
+        rank: 0,
+        face_up: false,
+    }
+    .index();
+}
+
+#[test]
+#[should_panic(expected = "`Pinned` holds a value outside its Enumly `range`")]
+fn single_value_ranges_reject_other_values() {
+    Pinned { slot: 1 }.index();
+}
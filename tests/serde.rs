@@ -0,0 +1,85 @@
+//! Every serde representation reads back what it writes.
+
+use enumly::Enumly;
+use serde_json::json;
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(serde = "name", from_str, rename_all = "lowercase")]
+enum Level {
+    Low,
+    #[enumly(alias = "hi")]
+    High,
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(serde = "index")]
+enum Cell {
+    Empty,
+    Filled(bool),
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(serde = "discriminant")]
+enum Temperature {
+    Cold = -5,
+    Warm = 3,
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(serde = "index")]
+enum Source {
+    Disk,
+    #[enumly(skip)]
+    Remote(u16),
+}
+
+#[test]
+fn name_round_trips_and_accepts_aliases() {
+    assert_eq!(serde_json::to_value(Level::High).unwrap(), json!("high"));
+    for level in Level::VARIANTS {
+        let value = serde_json::to_value(level).unwrap();
+        assert_eq!(serde_json::from_value::<Level>(value).unwrap(), *level);
+    }
+    assert_eq!(serde_json::from_value::<Level>(json!("hi")).unwrap(), Level::High);
+}
+
+#[test]
+fn index_round_trips_flattened_variants() {
+    let indices: Vec<_> = Cell::VARIANTS
+        .iter()
+        .map(|cell| serde_json::to_value(cell).unwrap())
+        .collect();
+    assert_eq!(indices, [json!(0), json!(1), json!(2)]);
+    for (cell, index) in Cell::VARIANTS.iter().zip(indices) {
+        assert_eq!(serde_json::from_value::<Cell>(index).unwrap(), *cell);
+    }
+}
+
+#[test]
+fn discriminant_round_trips_negative_values() {
+    assert_eq!(serde_json::to_value(Temperature::Cold).unwrap(), json!(-5));
+    for temperature in Temperature::VARIANTS {
+        let value = serde_json::to_value(temperature).unwrap();
+        assert_eq!(serde_json::from_value::<Temperature>(value).unwrap(), *temperature);
+    }
+}
+
+#[test]
+fn skipped_variants_cannot_be_serialized() {
+    let err = serde_json::to_value(Source::Remote(8080)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Source::Remote` is skipped by Enumly and cannot be serialized"
+    );
+    assert_eq!(serde_json::to_value(Source::Disk).unwrap(), json!(0));
+}
+
+#[test]
+fn unknown_values_are_rejected() {
+    let err = serde_json::from_value::<Cell>(json!(3)).unwrap_err();
+    assert_eq!(err.to_string(), "invalid Cell index `3`");
+    let err = serde_json::from_value::<Temperature>(json!(0)).unwrap_err();
+    assert_eq!(err.to_string(), "invalid Temperature discriminant `0`");
+    let err = serde_json::from_value::<Level>(json!("medium")).unwrap_err();
+    assert!(err.to_string().contains("unknown variant `medium`"), "{err}");
+}
@@ -0,0 +1,102 @@
+//! Word sizing, and set operations that must not reach past the last variant.
+
+use std::mem::size_of;
+
+use enumly::Enumly;
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(set = TrioSet)]
+enum Trio {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(set = WideSet)]
+enum Wide {
+    W0, W1, W2, W3, W4, W5, W6, W7, W8, W9,
+    W10, W11, W12, W13, W14, W15, W16, W17, W18, W19,
+    W20, W21, W22, W23, W24, W25, W26, W27, W28, W29,
+    W30, W31, W32, W33, W34, W35, W36, W37, W38, W39,
+    W40, W41, W42, W43, W44, W45, W46, W47, W48, W49,
+    W50, W51, W52, W53, W54, W55, W56, W57, W58, W59,
+    W60, W61, W62, W63, W64, W65, W66, W67, W68, W69,
+    W70, W71, W72, W73, W74, W75, W76, W77, W78, W79,
+    W80, W81, W82, W83, W84, W85, W86, W87, W88, W89,
+    W90, W91, W92, W93, W94, W95, W96, W97, W98, W99,
+    W100, W101, W102, W103, W104, W105, W106, W107, W108, W109,
+    W110, W111, W112, W113, W114, W115, W116, W117, W118, W119,
+    W120, W121, W122, W123, W124, W125, W126, W127, W128, W129,
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(set = SwitchSet)]
+enum Switch {
+    Off,
+    On(bool),
+}
+
+#[derive(Enumly, Clone, Copy, Debug, PartialEq)]
+#[enumly(set = PairSet)]
+struct Pair {
+    left: bool,
+    right: bool,
+}
+
+#[test]
+fn words_fit_the_variant_count() {
+    assert_eq!(size_of::<TrioSet>(), 1);
+    assert_eq!(size_of::<WideSet>(), 3 * size_of::<u64>());
+    assert_eq!(WideSet::all().len(), 130);
+    assert_eq!(WideSet::all().iter().next_back(), Some(Wide::W129));
+}
+
+#[test]
+fn flattened_types_use_u64_words() {
+    assert_eq!(size_of::<SwitchSet>(), size_of::<u64>());
+    assert_eq!(size_of::<PairSet>(), size_of::<u64>());
+    assert_eq!(SwitchSet::all().len(), 3);
+
+    let set = PairSet::from_slice(&[Pair { left: true, right: false }]);
+    assert_eq!(format!("{set:?}"), "{2}");
+}
+
+#[test]
+fn complement_stays_within_the_variants() {
+    assert_eq!((!TrioSet::empty()).len(), 3);
+    assert!((!TrioSet::all()).is_empty());
+    assert_eq!(
+        (!TrioSet::from_slice(&[Trio::Second])).iter().collect::<Vec<_>>(),
+        [Trio::First, Trio::Third]
+    );
+
+    let wide = !WideSet::from_slice(&[Wide::W0, Wide::W129]);
+    assert_eq!(wide.len(), 128);
+    assert!(!wide.contains(&Wide::W129));
+    assert_eq!(!wide, WideSet::from_slice(&[Wide::W0, Wide::W129]));
+    assert_eq!((!SwitchSet::empty()).len(), 3);
+}
+
+#[test]
+fn subsets_are_checked_across_words() {
+    let low = WideSet::from_slice(&[Wide::W1, Wide::W64]);
+    let high = low | WideSet::from_slice(&[Wide::W128]);
+    assert!(low.is_subset(&high));
+    assert!(!high.is_subset(&low));
+    assert!(WideSet::empty().is_subset(&low));
+    assert!(high.is_subset(&WideSet::all()));
+    assert!(!WideSet::all().is_subset(&high));
+}
+
+#[test]
+fn insert_and_remove_report_changes() {
+    let mut set = TrioSet::empty();
+    assert!(set.insert(Trio::Third));
+    assert!(!set.insert(Trio::Third));
+    assert!(set.contains(&Trio::Third));
+    assert!(!set.contains(&Trio::First));
+    assert!(set.remove(Trio::Third));
+    assert!(!set.remove(Trio::Third));
+    assert!(set.is_empty());
+}
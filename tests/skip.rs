@@ -0,0 +1,33 @@
+//! Skipped variants may carry data that needs dropping, which the generated items never touch.
+
+use enumly::Enumly;
+
+#[derive(Enumly, Clone, Debug, PartialEq)]
+#[enumly(map = LevelMap, set = LevelSet)]
+enum Level {
+    Low,
+    High,
+    #[enumly(skip)]
+    Unknown(String),
+}
+
+#[test]
+fn items_accept_enums_with_drop_glue() {
+    assert_eq!(Level::VARIANTS, &[Level::Low, Level::High]);
+    assert_eq!(Level::High.next_wrapping(), Level::Low);
+
+    let mut map = LevelMap::from_fn(|level| level.index());
+    *map.get_mut(&Level::High) += 10;
+    assert_eq!(*map.get(&Level::High), 11);
+
+    let mut set = LevelSet::empty();
+    assert!(set.insert(Level::High));
+    assert!(set.contains(&Level::High));
+    assert!(set.remove(Level::High));
+}
+
+#[test]
+#[should_panic(expected = "`Level::Unknown` is skipped by Enumly and has no index")]
+fn index_of_a_skipped_variant_panics() {
+    Level::Unknown(String::from("legacy")).index();
+}
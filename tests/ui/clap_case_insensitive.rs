@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly, Clone)]
+#[enumly(clap, ascii_case_insensitive)]
+enum Bad {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly, Clone)]
+#[enumly(clap)]
+enum Bad {
+    A(bool),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use enumly::Enumly;
+
+mod text {
+    pub struct String;
+}
+
+#[derive(Enumly)]
+enum Bad {
+    #[enumly(default_fields)]
+    Note { body: text::String },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(display = "{colour}")]
+enum Bad {
+    A,
+}
+
+fn main() {}
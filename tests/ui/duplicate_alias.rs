@@ -0,0 +1,11 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Bad {
+    #[enumly(alias = "b")]
+    Blue,
+    #[enumly(alias = "b")]
+    Black,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Bad {
+    Bytes(Vec<u8>),
+    Boxed(Box<bool>),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Bad {
+    COUNT,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(count = "NAMES")]
+enum Counted {
+    A,
+}
+
+#[derive(Enumly)]
+#[enumly(variants = "DISCRIMINANTS")]
+enum Listed {
+    A,
+}
+
+#[derive(Enumly)]
+#[enumly(names = "LIST", docs = "LIST")]
+enum Documented {
+    A,
+}
+
+#[derive(Enumly)]
+#[enumly(variants = "index")]
+struct Flags(bool);
+
+fn main() {}
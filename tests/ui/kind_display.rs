@@ -0,0 +1,18 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(kind = MsgKind)]
+enum Msg {
+    #[enumly(display = "text!")]
+    Text(String),
+    Quit,
+}
+
+#[derive(Enumly)]
+#[enumly(kind = OtherKind, display)]
+enum Other {
+    Text(String),
+    Quit,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(kind = MsgKind, iter = Msgs)]
+enum Msg {
+    Text(String),
+    Quit,
+}
+
+fn main() {}
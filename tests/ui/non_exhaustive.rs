@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[non_exhaustive]
+enum Bad {
+    A,
+}
+
+fn main() {}
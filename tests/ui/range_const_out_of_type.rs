@@ -0,0 +1,11 @@
+use enumly::Enumly;
+
+const FIRST: i32 = -1;
+
+#[derive(Enumly)]
+struct Bad {
+    #[enumly(range = FIRST..2)]
+    level: u8,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Bad {
+    Level(#[enumly(range = 5..=1)] u8),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+struct Bad {
+    #[enumly(range = -2..-2)]
+    offset: i8,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Bad {
+    Level(#[enumly(range = 250..=260)] u8),
+}
+
+fn main() {}
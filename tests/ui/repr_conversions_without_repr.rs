@@ -0,0 +1,10 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(repr_conversions)]
+enum Bad {
+    A,
+    B,
+}
+
+fn main() {}
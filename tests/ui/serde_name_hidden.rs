@@ -0,0 +1,11 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(serde = "name", from_str)]
+enum Bad {
+    Current,
+    #[enumly(hidden)]
+    Legacy,
+}
+
+fn main() {}
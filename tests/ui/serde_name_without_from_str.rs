@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(serde = "name")]
+enum Bad {
+    A,
+}
+
+fn main() {}
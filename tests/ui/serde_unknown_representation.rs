@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+#[enumly(serde = "ordinal")]
+enum Bad {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Holder<T> {
+    Empty,
+    #[enumly(skip)]
+    Full(T),
+}
+
+#[derive(Enumly)]
+enum Borrowed<'a> {
+    Empty,
+    #[enumly(skip)]
+    Text(&'a str),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+union Bad {
+    a: u8,
+    b: u16,
+}
+
+fn main() {}
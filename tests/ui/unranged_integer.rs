@@ -0,0 +1,8 @@
+use enumly::Enumly;
+
+#[derive(Enumly)]
+enum Bad {
+    Struct { value: u8 },
+}
+
+fn main() {}
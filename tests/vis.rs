@@ -0,0 +1,25 @@
+//! A `vis` narrower than the enum must not narrow the error types of its trait impls.
+
+pub mod codes {
+    use enumly::Enumly;
+
+    #[derive(Enumly, Debug, PartialEq)]
+    #[enumly(vis = "pub(crate)", from_str, repr_conversions)]
+    #[repr(u8)]
+    pub enum Code {
+        Success = 0,
+        Failure = 1,
+    }
+}
+
+use codes::{Code, ParseCodeError, TryFromCodeError};
+
+#[test]
+fn error_types_keep_the_enum_visibility() {
+    let parsed: Result<Code, ParseCodeError> = "Success".parse();
+    assert_eq!(parsed, Ok(Code::Success));
+
+    let converted: Result<Code, TryFromCodeError> = Code::try_from(2);
+    assert_eq!(converted.unwrap_err().value(), 2);
+    assert_eq!(Code::COUNT, 2);
+}